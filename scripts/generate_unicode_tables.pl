@@ -0,0 +1,81 @@
+#!/usr/bin/env perl
+# Generates src/unicode/tables.rs from the Unicode Character Database bundled with Perl.
+#
+# Usage: perl scripts/generate_unicode_tables.pl > src/unicode/tables.rs
+
+use strict;
+use warnings;
+use Unicode::UCD qw(prop_values prop_value_aliases prop_invlist);
+
+# Returns the ranges of an inversion list as pairs of inclusive bounds, without surrogates,
+# which are not chars. As in CharSet, ranges are merged wherever no char lies between them,
+# including across the surrogates, so each set of chars has exactly one table.
+sub ranges {
+    my @invlist = @_;
+    push @invlist, 0x110000 if @invlist % 2;
+    my @ranges;
+    for (my $i = 0; $i < @invlist; $i += 2) {
+        my ($start, $end) = ($invlist[$i], $invlist[$i + 1] - 1);
+        $start = 0xE000 if $start >= 0xD800 && $start <= 0xDFFF;
+        $end = 0xD7FF if $end >= 0xD800 && $end <= 0xDFFF;
+        push @ranges, [$start, $end] if $start <= $end;
+    }
+    my @merged;
+    for my $range (sort { $a->[0] <=> $b->[0] } @ranges) {
+        my $next = @merged ? ($merged[-1][1] == 0xD7FF ? 0xE000 : $merged[-1][1] + 1) : undef;
+        if (@merged && $next >= $range->[0]) {
+            $merged[-1][1] = $range->[1] if $range->[1] > $merged[-1][1];
+        } else {
+            push @merged, [@$range];
+        }
+    }
+    return @merged;
+}
+
+# Writes a table of ranges, several to a line.
+sub print_ranges {
+    my ($name, @ranges) = @_;
+    print "const $name: &[(char, char)] = &[\n";
+    my @items = map { sprintf("('\\u{%X}', '\\u{%X}')", @$_) } @ranges;
+    while (my @line = splice(@items, 0, 4)) {
+        print "    ", join(", ", @line), ",\n";
+    }
+    print "];\n\n";
+}
+
+my $version = Unicode::UCD::UnicodeVersion();
+print "//! Unicode $version property tables, generated by scripts/generate_unicode_tables.pl.\n";
+print "//!\n//! Do not edit by hand.\n\n";
+
+my @categories;
+for my $short (sort(prop_values("gc"))) {
+    # Only the two-letter categories are stored; the others are unions of them.
+    next unless length($short) == 2 && $short ne "LC";
+    my (undef, $long) = prop_value_aliases("gc", $short);
+    my $const = uc("GC_$short");
+    print_ranges($const, ranges(prop_invlist("gc=$short")));
+    push @categories, [$short, $long, $const];
+}
+
+my @scripts;
+for my $short (sort(prop_values("Script"))) {
+    my (undef, $long) = prop_value_aliases("Script", $short);
+    my @ranges = ranges(prop_invlist("Script=$short"));
+    # Some scripts, such as Katakana_Or_Hiragana, only occur in the Script_Extensions property.
+    next unless @ranges && defined $long;
+    my $const = uc("SC_$short");
+    print_ranges($const, @ranges);
+    push @scripts, [$short, $long, $const];
+}
+
+print "/// A property, by short name and long name, with the ranges of chars having it.\n";
+print "pub(crate) type Property = (&'static str, &'static str, &'static [(char, char)]);\n\n";
+print "/// The two-letter general categories, by short name and long name.\n";
+print "pub(crate) const GENERAL_CATEGORIES: &[Property] = &[\n";
+printf("    (\"%s\", \"%s\", %s),\n", @$_) for @categories;
+print "];\n\n";
+
+print "/// The scripts, by short name and long name.\n";
+print "pub(crate) const SCRIPTS: &[Property] = &[\n";
+printf("    (\"%s\", \"%s\", %s),\n", @$_) for @scripts;
+print "];\n";
@@ -1,6 +1,7 @@
 //! Annotates an AST of [`Expression`] nodes for transformation.
 
-use crate::parser::Expression;
+use crate::char_set::CharSet;
+use crate::parser::{Assertion, Expression};
 use std::{
     collections::HashSet,
     fmt::{Display, Formatter},
@@ -15,7 +16,15 @@ mod tests;
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) enum AnnotatedExpressionType<T> {
     Char(char, usize),
+    /// Represents a single position matching any char of a set which is in the alphabet.
+    Class(CharSet, usize),
     EmptyString(usize),
+    /// Represents the empty language. Like the empty string, it never matches a char, but
+    /// unlike it, it is not nullable.
+    EmptySet(usize),
+    /// Represents a zero-width assertion. Like a char, it is a position which is not nullable,
+    /// but it is passed through without consuming a char wherever it holds.
+    Assertion(Assertion, usize),
     /// Represents the end of the regular expression. This is only necessary for
     /// creating the DFA.
     Terminal(usize),
@@ -80,6 +89,15 @@ pub enum AnnotationError {
     InvalidExpression(InvalidExpressionError),
 }
 
+impl Display for AnnotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            AnnotationError::NodeOverflow(e) => write!(f, "{}", e),
+            AnnotationError::InvalidExpression(e) => write!(f, "{}", e),
+        }
+    }
+}
+
 /// Recursively annotates an input expression.
 fn annotate_expression(
     expression: Expression,
@@ -107,6 +125,46 @@ fn annotate_expression(
                 }),
             }
         }
+        Expression::Class(chars) => {
+            let next_expression = Rc::from(AnnotatedExpression {
+                expression: AnnotatedExpressionType::Class(chars, *next_index),
+                is_nullable: false,
+                matches_start: HashSet::from([*next_index]),
+                matches_end: HashSet::from([*next_index]),
+            });
+            *next_index += 1;
+            let mut next_leaves = leaves;
+            next_leaves.push(Rc::clone(&next_expression));
+            match next_index {
+                0 => Err(AnnotationError::NodeOverflow(NodeOverflowError {
+                    size: *next_index - 1,
+                })),
+                _ => Ok(AnnotatedExpressionContext {
+                    expression: Rc::clone(&next_expression),
+                    leaves: next_leaves,
+                }),
+            }
+        }
+        Expression::Assertion(assertion) => {
+            let next_expression = Rc::from(AnnotatedExpression {
+                expression: AnnotatedExpressionType::Assertion(assertion, *next_index),
+                is_nullable: false,
+                matches_start: HashSet::from([*next_index]),
+                matches_end: HashSet::from([*next_index]),
+            });
+            *next_index += 1;
+            let mut next_leaves = leaves;
+            next_leaves.push(Rc::clone(&next_expression));
+            match next_index {
+                0 => Err(AnnotationError::NodeOverflow(NodeOverflowError {
+                    size: *next_index - 1,
+                })),
+                _ => Ok(AnnotatedExpressionContext {
+                    expression: Rc::clone(&next_expression),
+                    leaves: next_leaves,
+                }),
+            }
+        }
         Expression::EmptyString => {
             let next_expression = Rc::from(AnnotatedExpression {
                 expression: AnnotatedExpressionType::EmptyString(*next_index),
@@ -127,6 +185,26 @@ fn annotate_expression(
                 }),
             }
         }
+        Expression::EmptySet => {
+            let next_expression = Rc::from(AnnotatedExpression {
+                expression: AnnotatedExpressionType::EmptySet(*next_index),
+                is_nullable: false,
+                matches_start: HashSet::new(),
+                matches_end: HashSet::new(),
+            });
+            *next_index += 1;
+            let mut next_leaves = leaves;
+            next_leaves.push(Rc::clone(&next_expression));
+            match next_index {
+                0 => Err(AnnotationError::NodeOverflow(NodeOverflowError {
+                    size: *next_index - 1,
+                })),
+                _ => Ok(AnnotatedExpressionContext {
+                    expression: Rc::clone(&next_expression),
+                    leaves: next_leaves,
+                }),
+            }
+        }
         Expression::Closure(sub_expression) => {
             let internal_expression = annotate_expression(*sub_expression, next_index, leaves)?;
             let next_expression = Rc::from(AnnotatedExpression {
@@ -227,13 +305,12 @@ pub(crate) fn annotate_ast(
         matches_end: HashSet::from([expression.leaves.len()]),
     });
     let mut combined_matches_start = HashSet::new();
-    let mut combined_matches_end = HashSet::new();
     if expression.expression.is_nullable {
         combined_matches_start.extend(terminal.matches_start.iter().copied());
-        combined_matches_end.extend(expression.expression.matches_end.iter().copied());
     }
     combined_matches_start.extend(expression.expression.matches_start.iter().copied());
-    combined_matches_end.extend(terminal.matches_end.iter().copied());
+    // The terminal is not nullable, so it is the only node which can match the end.
+    let combined_matches_end = terminal.matches_end.clone();
     let combined_expression = Rc::from(AnnotatedExpression {
         expression: AnnotatedExpressionType::Concatenation(vec![
             Rc::clone(&expression.expression),
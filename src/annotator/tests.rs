@@ -63,6 +63,20 @@ fn test_empty_string_expression_annotation() {
     assert_eq!(output.expression, output.leaves[0]);
 }
 
+#[test]
+fn test_empty_set_expression_annotation() {
+    let input = Expression::EmptySet;
+    let expected_annotated_expression = AnnotatedExpression {
+        expression: AnnotatedExpressionType::EmptySet(0),
+        is_nullable: false,
+        matches_start: HashSet::new(),
+        matches_end: HashSet::new(),
+    };
+    let output = unwrap_terminal(annotate_ast(input).unwrap()).unwrap();
+    assert_eq!(*output.expression, expected_annotated_expression);
+    assert_eq!(output.expression, output.leaves[0]);
+}
+
 #[test]
 fn test_concatenation_expression_annotation() {
     let input = Expression::Concatenation(vec![Expression::Char('a'), Expression::Char('b')]);
@@ -158,3 +172,13 @@ fn test_closure_expression_annotation() {
     let output = unwrap_terminal(annotate_ast(input).unwrap()).unwrap();
     assert_eq!(*output.expression, expected_annotated_expression);
 }
+
+#[test]
+fn test_nullable_root_matches_end() {
+    // Only the terminal can match the end of the root, even when the expression itself is
+    // nullable and so could otherwise end at its own lastpos.
+    let input = Expression::Closure(Box::new(Expression::Char('a')));
+    let output = annotate_ast(input).unwrap();
+    assert_eq!(output.expression.matches_start, HashSet::from([0, 1]));
+    assert_eq!(output.expression.matches_end, HashSet::from([1]));
+}
@@ -0,0 +1,184 @@
+//! An arbitrary-precision unsigned integer, for counting the strings of a language.
+
+use crate::sample::RandomSource;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::{Add, AddAssign, SubAssign};
+
+#[cfg(test)]
+mod tests;
+
+/// The base of each limb of a [`BigCount`].
+const LIMB_BASE: u64 = 1 << 32;
+
+/// The largest power of ten which fits in a limb, used when formatting in decimal.
+const DECIMAL_CHUNK: u64 = 1_000_000_000;
+
+/// The number of decimal digits in [`DECIMAL_CHUNK`].
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// An arbitrary-precision unsigned integer.
+///
+/// The numbers of strings in regular languages grow exponentially with their length, so soon
+/// overflow any fixed-width integer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigCount {
+    /// The limbs of the number in base 2^32, least significant first, with no trailing zero
+    /// limbs. Zero is thus represented by no limbs.
+    limbs: Vec<u32>,
+}
+
+impl BigCount {
+    /// Returns zero.
+    pub fn zero() -> BigCount {
+        BigCount::default()
+    }
+
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns the number as a [`u64`], or [`None`] if it does not fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(u64::from(self.limbs[0])),
+            2 => Some(u64::from(self.limbs[0]) + (u64::from(self.limbs[1]) << 32)),
+            _ => None,
+        }
+    }
+
+    /// Removes trailing zero limbs, restoring the canonical representation.
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// Divides the number in place by a divisor which fits in a limb, returning the remainder.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) + u64::from(*limb);
+            *limb = (value / divisor) as u32;
+            remainder = value % divisor;
+        }
+        self.normalize();
+        remainder
+    }
+
+    /// Returns a number drawn uniformly at random from those less than bound, which must be
+    /// non-zero.
+    ///
+    /// Draws numbers with as many bits as the bound, rejecting any which are not less than it,
+    /// so on average fewer than two draws are needed.
+    pub(crate) fn random_below(bound: &BigCount, rng: &mut dyn RandomSource) -> BigCount {
+        assert!(!bound.is_zero(), "Cannot draw a number below zero.");
+        let top_limb = *bound.limbs.last().unwrap();
+        let top_mask = u32::MAX >> top_limb.leading_zeros();
+        loop {
+            let mut limbs: Vec<u32> = (0..bound.limbs.len()).map(|_| rng.next_u32()).collect();
+            *limbs.last_mut().unwrap() &= top_mask;
+            let mut candidate = BigCount { limbs };
+            candidate.normalize();
+            if candidate < *bound {
+                return candidate;
+            }
+        }
+    }
+}
+
+impl From<u64> for BigCount {
+    fn from(value: u64) -> Self {
+        let mut count = BigCount {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        count.normalize();
+        count
+    }
+}
+
+impl AddAssign<&BigCount> for BigCount {
+    fn add_assign(&mut self, rhs: &BigCount) {
+        if self.limbs.len() < rhs.limbs.len() {
+            self.limbs.resize(rhs.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum = u64::from(*limb) + u64::from(rhs.limbs.get(i).copied().unwrap_or(0)) + carry;
+            *limb = (sum % LIMB_BASE) as u32;
+            carry = sum / LIMB_BASE;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl Add<&BigCount> for BigCount {
+    type Output = BigCount;
+
+    fn add(mut self, rhs: &BigCount) -> BigCount {
+        self += rhs;
+        self
+    }
+}
+
+impl SubAssign<&BigCount> for BigCount {
+    /// Subtracts a number which is no greater than this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result would be negative.
+    fn sub_assign(&mut self, rhs: &BigCount) {
+        assert!(*self >= *rhs, "BigCount subtraction overflowed.");
+        let mut borrow = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let subtrahend = u64::from(rhs.limbs.get(i).copied().unwrap_or(0)) + borrow;
+            let value = u64::from(*limb);
+            if value >= subtrahend {
+                *limb = (value - subtrahend) as u32;
+                borrow = 0;
+            } else {
+                *limb = (value + LIMB_BASE - subtrahend) as u32;
+                borrow = 1;
+            }
+        }
+        self.normalize();
+    }
+}
+
+impl Ord for BigCount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigCount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigCount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let mut remaining = self.clone();
+        let mut chunks = Vec::new();
+        loop {
+            chunks.push(remaining.div_rem_small(DECIMAL_CHUNK));
+            if remaining.is_zero() {
+                break;
+            }
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
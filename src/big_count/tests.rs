@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_small_values() {
+    assert_eq!(BigCount::zero().to_string(), "0");
+    assert!(BigCount::zero().is_zero());
+    assert_eq!(BigCount::from(0), BigCount::zero());
+    assert_eq!(BigCount::from(42).to_string(), "42");
+    assert_eq!(BigCount::from(u64::MAX).to_u64(), Some(u64::MAX));
+}
+
+#[test]
+fn test_addition_carries_between_limbs() {
+    let sum = BigCount::from(u64::MAX) + &BigCount::from(1);
+    assert_eq!(sum.to_u64(), None);
+    assert_eq!(sum.to_string(), "18446744073709551616");
+    let mut doubled = sum.clone();
+    doubled += &sum;
+    assert_eq!(doubled.to_string(), "36893488147419103232");
+}
+
+#[test]
+fn test_large_powers_of_two() {
+    let mut power = BigCount::from(1);
+    for _ in 0..100 {
+        power += &power.clone();
+    }
+    assert_eq!(power.to_string(), "1267650600228229401496703205376");
+}
+
+#[test]
+fn test_ordering() {
+    let small = BigCount::from(u64::MAX);
+    let large = small.clone() + &BigCount::from(1);
+    assert!(small < large);
+    assert!(BigCount::from(3) > BigCount::from(2));
+    assert_eq!(large.cmp(&large.clone()), Ordering::Equal);
+}
+
+#[test]
+fn test_formatting_width() {
+    assert_eq!(format!("{:>5}", BigCount::from(42)), "   42");
+}
+
+#[test]
+fn test_subtraction_borrows_between_limbs() {
+    let mut difference = BigCount::from(u64::MAX) + &BigCount::from(1);
+    difference -= &BigCount::from(1);
+    assert_eq!(difference, BigCount::from(u64::MAX));
+    difference -= &BigCount::from(u64::MAX);
+    assert!(difference.is_zero());
+}
+
+#[test]
+#[should_panic]
+fn test_subtraction_below_zero_panics() {
+    let mut difference = BigCount::from(1);
+    difference -= &BigCount::from(2);
+}
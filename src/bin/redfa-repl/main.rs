@@ -0,0 +1,5 @@
+//! Interactive REPL for exploring each stage of the regular expression to DFA pipeline.
+
+fn main() -> std::io::Result<()> {
+    regular_expression::repl::run(&mut std::io::stdin().lock(), &mut std::io::stdout().lock())
+}
@@ -0,0 +1,177 @@
+//! Command-line tool for compiling regular expressions to DFAs, matching input against them
+//! and inspecting the result.
+
+use regular_expression::{generate_dfa, Dfa, DfaGenerationError};
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::{BufRead, Write};
+use std::process::ExitCode;
+
+#[cfg(test)]
+mod tests;
+
+const USAGE: &str = "\
+Usage: redfa <command> <expression>... --alphabet <alphabet>
+
+Commands:
+    compile <expression>          Print the state table of the DFA.
+    match <expression>            Print each line of stdin accepted by the DFA.
+    dot <expression>              Print the DFA as a Graphviz DOT graph.
+    minimize <expression>         Print the state table of the minimal DFA.
+    equiv <expression> <expression>
+                                  Print whether the two expressions are equivalent.
+
+Options:
+    -a, --alphabet <alphabet>     The chars of the alphabet, e.g. \"ab\".
+    -h, --help                    Print this message.
+
+Exit status is 0 on success, 1 if match accepts no line or equiv finds the expressions
+inequivalent, and 2 on error.";
+
+/// Exit status for a successful command.
+const SUCCESS: u8 = 0;
+/// Exit status for a command which ran but found no match or no equivalence.
+const NO_MATCH: u8 = 1;
+/// Exit status for a command which failed.
+const FAILURE: u8 = 2;
+
+/// Wraps all errors which can end a command.
+#[derive(Debug)]
+enum CliError {
+    /// Represents that the arguments do not form a valid command.
+    Usage(String),
+    /// Represents that an expression could not be compiled to a DFA.
+    Generation(DfaGenerationError),
+    /// Represents a failure to read input or write output.
+    Io(std::io::Error),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            CliError::Generation(e) => write!(f, "{}", e),
+            CliError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<DfaGenerationError> for CliError {
+    fn from(value: DfaGenerationError) -> Self {
+        CliError::Generation(value)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(value: std::io::Error) -> Self {
+        CliError::Io(value)
+    }
+}
+
+/// Represents a parsed command line.
+#[derive(Debug, PartialEq)]
+struct Command {
+    name: String,
+    expressions: Vec<String>,
+    alphabet: String,
+}
+
+/// Parses the arguments following the program name into a [`Command`], or [`None`] if help
+/// was requested.
+fn parse_args(args: &[String]) -> Result<Option<Command>, CliError> {
+    let mut positional = Vec::new();
+    let mut alphabet = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-a" | "--alphabet" => match args.next() {
+                Some(value) => alphabet = Some(value.clone()),
+                None => {
+                    return Err(CliError::Usage(format!("Missing value for {}.", arg)));
+                }
+            },
+            _ => positional.push(arg.clone()),
+        }
+    }
+    if positional.is_empty() {
+        return Err(CliError::Usage(String::from("Missing command.")));
+    }
+    let name = positional.remove(0);
+    let n_expressions = match name.as_str() {
+        "compile" | "match" | "dot" | "minimize" => 1,
+        "equiv" => 2,
+        _ => return Err(CliError::Usage(format!("Unknown command {}.", name))),
+    };
+    if positional.len() != n_expressions {
+        return Err(CliError::Usage(format!(
+            "{} expects {} expression(s) but received {}.",
+            name,
+            n_expressions,
+            positional.len()
+        )));
+    }
+    let Some(alphabet) = alphabet else {
+        return Err(CliError::Usage(String::from("Missing --alphabet.")));
+    };
+    Ok(Some(Command {
+        name,
+        expressions: positional,
+        alphabet,
+    }))
+}
+
+/// Runs the command described by args, returning the exit status.
+fn run(args: &[String], input: &mut dyn BufRead, output: &mut dyn Write) -> Result<u8, CliError> {
+    let Some(command) = parse_args(args)? else {
+        writeln!(output, "{}", USAGE)?;
+        return Ok(SUCCESS);
+    };
+    let dfas = command
+        .expressions
+        .iter()
+        .map(|expression| generate_dfa(expression, &command.alphabet))
+        .collect::<Result<Vec<Dfa>, DfaGenerationError>>()?;
+    match command.name.as_str() {
+        "compile" => writeln!(output, "{}", dfas[0])?,
+        "minimize" => writeln!(output, "{}", dfas[0].minimize())?,
+        "dot" => writeln!(output, "{}", dfas[0].to_dot())?,
+        "match" => {
+            let mut status = NO_MATCH;
+            for line in input.lines() {
+                let line = line?;
+                if dfas[0].accepts(&line) {
+                    writeln!(output, "{}", line)?;
+                    status = SUCCESS;
+                }
+            }
+            return Ok(status);
+        }
+        "equiv" => {
+            if dfas[0].is_equivalent(&dfas[1]) {
+                writeln!(output, "equivalent")?;
+            } else {
+                writeln!(output, "not equivalent")?;
+                return Ok(NO_MATCH);
+            }
+        }
+        _ => unreachable!("parse_args only accepts known commands"),
+    }
+    Ok(SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let status = match run(
+        &args,
+        &mut std::io::stdin().lock(),
+        &mut std::io::stdout().lock(),
+    ) {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("redfa: {}", e);
+            FAILURE
+        }
+    };
+    ExitCode::from(status)
+}
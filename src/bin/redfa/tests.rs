@@ -0,0 +1,79 @@
+use super::*;
+
+fn run_with_input(args: &[&str], input: &str) -> (Result<u8, CliError>, String) {
+    let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+    let mut output = Vec::new();
+    let status = run(&args, &mut input.as_bytes(), &mut output);
+    (status, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn test_compile() {
+    let (status, output) = run_with_input(&["compile", "a|b", "--alphabet", "ab"], "");
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert_eq!(output, "      a  b\n-> 0  1  1\n  *1  -  -\n");
+}
+
+#[test]
+fn test_minimize() {
+    let (status, output) = run_with_input(&["minimize", "-a", "ab", "aa|ba"], "");
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert_eq!(output, "      a  b\n-> 0  1  1\n   1  2  -\n  *2  -  -\n");
+}
+
+#[test]
+fn test_match() {
+    let input = "abb\nab\naabb\r\n\nb\n";
+    let (status, output) = run_with_input(&["match", "(a|b)*abb", "-a", "ab"], input);
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert_eq!(output, "abb\naabb\n");
+    let (status, output) = run_with_input(&["match", "a", "-a", "ab"], input);
+    assert_eq!(status.unwrap(), NO_MATCH);
+    assert_eq!(output, "");
+}
+
+#[test]
+fn test_dot() {
+    let (status, output) = run_with_input(&["dot", "a", "-a", "a"], "");
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert!(output.starts_with("digraph dfa {\n"));
+    assert!(output.contains("    0 -> 1 [label=\"a\"];\n"));
+}
+
+#[test]
+fn test_equiv() {
+    let (status, output) = run_with_input(&["equiv", "(a|b)*", "(a*b*)*", "-a", "ab"], "");
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert_eq!(output, "equivalent\n");
+    let (status, output) = run_with_input(&["equiv", "a*", "(a|b)*", "-a", "ab"], "");
+    assert_eq!(status.unwrap(), NO_MATCH);
+    assert_eq!(output, "not equivalent\n");
+}
+
+#[test]
+fn test_usage_errors() {
+    let cases: [&[&str]; 5] = [
+        &[],
+        &["compile", "a"],
+        &["compile", "-a"],
+        &["equiv", "a", "-a", "a"],
+        &["frobnicate", "a", "-a", "a"],
+    ];
+    for args in cases {
+        let (status, _) = run_with_input(args, "");
+        assert!(matches!(status, Err(CliError::Usage(_))), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_generation_error() {
+    let (status, _) = run_with_input(&["compile", "c", "-a", "ab"], "");
+    assert!(matches!(status, Err(CliError::Generation(_))));
+}
+
+#[test]
+fn test_help() {
+    let (status, output) = run_with_input(&["--help"], "");
+    assert_eq!(status.unwrap(), SUCCESS);
+    assert!(output.starts_with("Usage: redfa"));
+}
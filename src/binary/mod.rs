@@ -0,0 +1,343 @@
+//! Saves a [`Dfa`] to, and loads it from, a compact binary format.
+
+use crate::dfa::Dfa;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+#[cfg(test)]
+mod tests;
+
+/// The bytes which start every encoded DFA.
+const MAGIC: &[u8; 4] = b"RDFA";
+
+/// The version of the format written by [`Dfa::to_bytes`].
+const VERSION: u32 = 1;
+
+/// The number of bytes before the alphabet.
+const HEADER_LENGTH: usize = 24;
+
+/// Wraps all errors which can occur while loading a [`Dfa`] from bytes.
+///
+/// Offsets are of the first byte of the invalid field.
+#[derive(Debug, PartialEq)]
+pub enum DfaDecodingError {
+    /// Represents that the bytes do not start with the magic number `RDFA`.
+    InvalidMagic,
+    /// Represents that the version of the format is not one this crate can load.
+    UnsupportedVersion(u32),
+    /// Represents that the number of bytes is not that given by the counts in the header.
+    InvalidLength {
+        /// The number of bytes given by the header.
+        expected: usize,
+        /// The number of bytes found.
+        found: usize,
+    },
+    /// Represents that a field which should be a char is not one, is out of order, or is a
+    /// transition on a char which is not in the alphabet.
+    InvalidChar {
+        /// The offset of the field.
+        offset: usize,
+    },
+    /// Represents that a field which should be a state is not less than the number of states,
+    /// or that the accepting bitset contains a state beyond the last.
+    StateOutOfRange {
+        /// The offset of the field.
+        offset: usize,
+    },
+    /// Represents that a transition offset is less than the one before it, or that the
+    /// offsets do not start at zero and end at the number of transitions.
+    InvalidTransitionOffset {
+        /// The offset of the field.
+        offset: usize,
+    },
+}
+
+impl Display for DfaDecodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            DfaDecodingError::InvalidMagic => {
+                write!(f, "The bytes do not start with the magic number RDFA.")
+            }
+            DfaDecodingError::UnsupportedVersion(version) => write!(
+                f,
+                "The bytes are of version {} of the format, but only version {} is supported.",
+                version, VERSION
+            ),
+            DfaDecodingError::InvalidLength { expected, found } => write!(
+                f,
+                "The header describes {} bytes, but {} were found.",
+                expected, found
+            ),
+            DfaDecodingError::InvalidChar { offset } => {
+                write!(f, "The char at byte offset {} is invalid.", offset)
+            }
+            DfaDecodingError::StateOutOfRange { offset } => {
+                write!(f, "The state at byte offset {} is out of range.", offset)
+            }
+            DfaDecodingError::InvalidTransitionOffset { offset } => write!(
+                f,
+                "The transition offset at byte offset {} is invalid.",
+                offset
+            ),
+        }
+    }
+}
+
+/// Reads the little-endian u32 at offset. The caller must have checked the length.
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// The counts and section offsets of validated bytes encoding a DFA.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Layout {
+    pub(crate) n_chars: usize,
+    pub(crate) n_states: usize,
+    pub(crate) start_state: usize,
+    pub(crate) alphabet: usize,
+    pub(crate) accepting_states: usize,
+    pub(crate) transition_offsets: usize,
+    pub(crate) transitions: usize,
+}
+
+impl Layout {
+    /// Returns the transitions of state as a range of transition indices.
+    pub(crate) fn transition_range(&self, bytes: &[u8], state: usize) -> std::ops::Range<usize> {
+        let offset = self.transition_offsets + 4 * state;
+        read_u32(bytes, offset) as usize..read_u32(bytes, offset + 4) as usize
+    }
+
+    /// Returns the char and target state of the transition with the given index.
+    pub(crate) fn transition(&self, bytes: &[u8], transition: usize) -> (u32, usize) {
+        let offset = self.transitions + 8 * transition;
+        (
+            read_u32(bytes, offset),
+            read_u32(bytes, offset + 4) as usize,
+        )
+    }
+
+    /// Returns whether state is accepting.
+    pub(crate) fn is_accepting(&self, bytes: &[u8], state: usize) -> bool {
+        bytes[self.accepting_states + state / 8] & (1 << (state % 8)) != 0
+    }
+
+    /// Checks that bytes are a valid encoding of a DFA, returning their layout.
+    ///
+    /// Runs in O(A + N + T log A) time for A chars, N states and T transitions, as each
+    /// transition's char is found in the alphabet by binary search, and never allocates.
+    pub(crate) fn validate(bytes: &[u8]) -> Result<Layout, DfaDecodingError> {
+        if !bytes.starts_with(MAGIC) {
+            return Err(DfaDecodingError::InvalidMagic);
+        }
+        if bytes.len() < HEADER_LENGTH {
+            return Err(DfaDecodingError::InvalidLength {
+                expected: HEADER_LENGTH,
+                found: bytes.len(),
+            });
+        }
+        let version = read_u32(bytes, 4);
+        if version != VERSION {
+            return Err(DfaDecodingError::UnsupportedVersion(version));
+        }
+        let [n_chars, n_states, start_state, n_transitions] =
+            [8, 12, 16, 20].map(|offset| read_u32(bytes, offset) as u64);
+        let expected_length = HEADER_LENGTH as u64
+            + 4 * n_chars
+            + n_states.div_ceil(8)
+            + 4 * (n_states + 1)
+            + 8 * n_transitions;
+        if expected_length != bytes.len() as u64 {
+            return Err(DfaDecodingError::InvalidLength {
+                expected: usize::try_from(expected_length).unwrap_or(usize::MAX),
+                found: bytes.len(),
+            });
+        }
+        // The length fits in memory, so every count does too.
+        let n_chars = n_chars as usize;
+        let n_states = n_states as usize;
+        let alphabet = HEADER_LENGTH;
+        let accepting_states = alphabet + 4 * n_chars;
+        let transition_offsets = accepting_states + n_states.div_ceil(8);
+        let layout = Layout {
+            n_chars,
+            n_states,
+            start_state: start_state as usize,
+            alphabet,
+            accepting_states,
+            transition_offsets,
+            transitions: transition_offsets + 4 * (n_states + 1),
+        };
+        if layout.start_state >= n_states {
+            return Err(DfaDecodingError::StateOutOfRange { offset: 16 });
+        }
+        let chars = (0..n_chars).map(|i| (alphabet + 4 * i, read_u32(bytes, alphabet + 4 * i)));
+        check_ascending_chars(chars)?;
+        // The bits after the last state in the last byte of the bitset must be unset.
+        let unused_states = n_states..8 * n_states.div_ceil(8);
+        if unused_states
+            .into_iter()
+            .any(|state| layout.is_accepting(bytes, state))
+        {
+            return Err(DfaDecodingError::StateOutOfRange {
+                offset: transition_offsets - 1,
+            });
+        }
+        if read_u32(bytes, transition_offsets) != 0 {
+            return Err(DfaDecodingError::InvalidTransitionOffset {
+                offset: transition_offsets,
+            });
+        }
+        for state in 0..n_states {
+            let offset = transition_offsets + 4 * (state + 1);
+            let range = layout.transition_range(bytes, state);
+            let is_last = state + 1 == n_states;
+            if range.end < range.start || range.end as u64 > n_transitions {
+                return Err(DfaDecodingError::InvalidTransitionOffset { offset });
+            }
+            if is_last && range.end as u64 != n_transitions {
+                return Err(DfaDecodingError::InvalidTransitionOffset { offset });
+            }
+            let chars = range.clone().map(|transition| {
+                let offset = layout.transitions + 8 * transition;
+                (offset, read_u32(bytes, offset))
+            });
+            check_ascending_chars(chars)?;
+            for transition in range {
+                let offset = layout.transitions + 8 * transition;
+                let (c, target_state) = layout.transition(bytes, transition);
+                if layout.find_char(bytes, c).is_none() {
+                    return Err(DfaDecodingError::InvalidChar { offset });
+                }
+                if target_state >= n_states {
+                    return Err(DfaDecodingError::StateOutOfRange { offset: offset + 4 });
+                }
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Returns the index in the alphabet of the char with code c, found by binary search.
+    pub(crate) fn find_char(&self, bytes: &[u8], c: u32) -> Option<usize> {
+        let (mut low, mut high) = (0, self.n_chars);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match read_u32(bytes, self.alphabet + 4 * middle).cmp(&c) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some(middle),
+            }
+        }
+        None
+    }
+}
+
+/// Checks that the codes, each given with its offset, are chars in strictly ascending order.
+fn check_ascending_chars<I>(codes: I) -> Result<(), DfaDecodingError>
+where
+    I: Iterator<Item = (usize, u32)>,
+{
+    let mut previous = None;
+    for (offset, code) in codes {
+        if char::from_u32(code).is_none() || previous.is_some_and(|previous| previous >= code) {
+            return Err(DfaDecodingError::InvalidChar { offset });
+        }
+        previous = Some(code);
+    }
+    Ok(())
+}
+
+impl Dfa {
+    /// Encodes the DFA in a compact binary format, which [`Dfa::from_bytes`] loads.
+    ///
+    /// Every integer is a little-endian `u32`, so the format is the same on every platform.
+    /// In order, the bytes are:
+    ///
+    /// | Size        | Field                                                                  |
+    /// |-------------|------------------------------------------------------------------------|
+    /// | 4           | The magic number `RDFA`.                                               |
+    /// | 4           | The version of the format, currently 1.                                |
+    /// | 4           | The number of chars in the alphabet, A.                                |
+    /// | 4           | The number of states, N.                                               |
+    /// | 4           | The start state.                                                       |
+    /// | 4           | The number of transitions, T.                                          |
+    /// | 4 × A       | The chars of the alphabet, in ascending order.                         |
+    /// | ⌈N / 8⌉     | The accepting states, as a bitset: state s is bit s % 8 of byte s / 8. |
+    /// | 4 × (N + 1) | The transition offsets: state s has the transitions from offset s up to offset s + 1. |
+    /// | 8 × T       | The transitions, each a char then the state it reaches, with those of each state in ascending order of char. |
+    ///
+    /// The output only depends on the DFA, not on the order of its hash maps, so equal DFAs
+    /// always give equal bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alphabet, states or transitions number more than [`u32::MAX`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let to_u32 = |n: usize| u32::try_from(n).expect("too large to encode");
+        let transitions: Vec<Vec<(char, usize)>> = (0..self.n_states)
+            .map(|state| self.sorted_transitions(state))
+            .collect();
+        let n_transitions: usize = transitions.iter().map(Vec::len).sum();
+        let mut bytes = MAGIC.to_vec();
+        for field in [
+            VERSION,
+            to_u32(self.alphabet.len()),
+            to_u32(self.n_states),
+            to_u32(self.start_state),
+            to_u32(n_transitions),
+        ] {
+            bytes.extend(field.to_le_bytes());
+        }
+        for c in &self.alphabet {
+            bytes.extend((*c as u32).to_le_bytes());
+        }
+        let mut accepting_states = vec![0u8; self.n_states.div_ceil(8)];
+        for state in &self.accepting_states {
+            accepting_states[state / 8] |= 1 << (state % 8);
+        }
+        bytes.extend(accepting_states);
+        let mut offset = 0;
+        bytes.extend(0u32.to_le_bytes());
+        for state_transitions in &transitions {
+            offset += state_transitions.len();
+            bytes.extend(to_u32(offset).to_le_bytes());
+        }
+        for (c, target_state) in transitions.into_iter().flatten() {
+            bytes.extend((c as u32).to_le_bytes());
+            bytes.extend(to_u32(target_state).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a DFA from the binary format described in [`Dfa::to_bytes`].
+    ///
+    /// The bytes are fully validated first, so any DFA returned is well formed: every state
+    /// is less than the number of states, and every transition is on a char of the alphabet.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Dfa, DfaDecodingError> {
+        let layout = Layout::validate(bytes)?;
+        let char_at = |offset| char::from_u32(read_u32(bytes, offset)).unwrap();
+        let alphabet: BTreeSet<char> = (0..layout.n_chars)
+            .map(|i| char_at(layout.alphabet + 4 * i))
+            .collect();
+        let accepting_states: HashSet<usize> = (0..layout.n_states)
+            .filter(|state| layout.is_accepting(bytes, *state))
+            .collect();
+        let mut transition_function: HashMap<usize, HashMap<char, usize>> = HashMap::new();
+        for state in 0..layout.n_states {
+            for transition in layout.transition_range(bytes, state) {
+                let (c, target_state) = layout.transition(bytes, transition);
+                transition_function
+                    .entry(state)
+                    .or_default()
+                    .insert(char::from_u32(c).unwrap(), target_state);
+            }
+        }
+        Ok(Dfa {
+            n_states: layout.n_states,
+            start_state: layout.start_state,
+            accepting_states,
+            transition_function,
+            alphabet,
+        })
+    }
+}
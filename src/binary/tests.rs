@@ -0,0 +1,108 @@
+use super::*;
+use crate::generate_dfa;
+
+/// Returns the encoding of the DFA of "a" over "ab".
+fn encoded_a() -> Vec<u8> {
+    let mut bytes = b"RDFA".to_vec();
+    for field in [1u32, 2, 2, 0, 1, 'a' as u32, 'b' as u32] {
+        bytes.extend(field.to_le_bytes());
+    }
+    bytes.push(0b10);
+    for field in [0u32, 1, 1, 'a' as u32, 1] {
+        bytes.extend(field.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn test_to_bytes() {
+    let dfa = generate_dfa("a", "ab").unwrap();
+    assert_eq!(dfa.to_bytes(), encoded_a());
+}
+
+#[test]
+fn test_round_trip() {
+    let cases = [
+        ("(a|b)*abb", "ab"),
+        ("", "ab"),
+        ("∅", "a"),
+        ("a⟹(🦀)*", "a⟹🦀"),
+        ("^(\\ba|b\\B| )*$", "ab "),
+        ("(abcdefgh)*i", "abcdefghi"),
+    ];
+    for (input_expression, alphabet) in cases {
+        let dfa = generate_dfa(input_expression, alphabet).unwrap();
+        let bytes = dfa.to_bytes();
+        assert_eq!(
+            Dfa::from_bytes(&bytes).unwrap(),
+            dfa,
+            "{}",
+            input_expression
+        );
+        // Equal DFAs are always encoded the same, whatever the order of their hash maps.
+        let regenerated = generate_dfa(input_expression, alphabet).unwrap();
+        assert_eq!(regenerated.to_bytes(), bytes, "{}", input_expression);
+    }
+}
+
+#[test]
+fn test_invalid_header() {
+    assert_eq!(
+        Dfa::from_bytes(b"RDFB"),
+        Err(DfaDecodingError::InvalidMagic)
+    );
+    assert_eq!(
+        Dfa::from_bytes(b"RDFA\x01"),
+        Err(DfaDecodingError::InvalidLength {
+            expected: HEADER_LENGTH,
+            found: 5
+        })
+    );
+    let mut bytes = encoded_a();
+    bytes[4] = 2;
+    assert_eq!(
+        Dfa::from_bytes(&bytes),
+        Err(DfaDecodingError::UnsupportedVersion(2))
+    );
+    let bytes = encoded_a();
+    assert_eq!(
+        Dfa::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(DfaDecodingError::InvalidLength {
+            expected: 53,
+            found: 52
+        })
+    );
+}
+
+#[test]
+fn test_invalid_fields() {
+    let cases = [
+        // The start state.
+        (16, 2, DfaDecodingError::StateOutOfRange { offset: 16 }),
+        // The alphabet is not in ascending order.
+        (28, b'a', DfaDecodingError::InvalidChar { offset: 28 }),
+        // The accepting bitset contains state 2.
+        (32, 0b110, DfaDecodingError::StateOutOfRange { offset: 32 }),
+        // The transitions of state 0 end beyond the last transition.
+        (
+            37,
+            2,
+            DfaDecodingError::InvalidTransitionOffset { offset: 37 },
+        ),
+        // A transition on a char which is not in the alphabet.
+        (45, b'c', DfaDecodingError::InvalidChar { offset: 45 }),
+        // A transition to state 2.
+        (49, 2, DfaDecodingError::StateOutOfRange { offset: 49 }),
+    ];
+    for (offset, byte, expected_error) in cases {
+        let mut bytes = encoded_a();
+        bytes[offset] = byte;
+        assert_eq!(Dfa::from_bytes(&bytes), Err(expected_error), "{}", offset);
+    }
+    let mut bytes = encoded_a();
+    bytes[24..28].copy_from_slice(&0xD800u32.to_le_bytes());
+    assert_eq!(
+        Dfa::from_bytes(&bytes),
+        Err(DfaDecodingError::InvalidChar { offset: 24 })
+    );
+}
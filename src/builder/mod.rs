@@ -0,0 +1,173 @@
+//! Builds regular expressions from code, without writing them in the string syntax.
+
+use crate::annotator::annotate_ast;
+use crate::char_set::CharSet;
+use crate::dfa::{generate_dfa, Dfa};
+use crate::options::GenerationOptions;
+use crate::parser::{Assertion, Expression};
+use crate::DfaGenerationError;
+use std::collections::BTreeSet;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents a regular expression built by combinators.
+///
+/// Any char can be used as a symbol, including those reserved by the string syntax, since no
+/// string is ever lexed. For example, `(a|b)*c` is built by
+/// `Regex::sym('a').or(Regex::sym('b')).star().then(Regex::sym('c'))`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regex {
+    expression: Expression,
+}
+
+impl Regex {
+    /// Returns the regex matching only the given char.
+    pub fn sym(c: char) -> Regex {
+        Regex {
+            expression: Expression::Char(c),
+        }
+    }
+
+    /// Returns the regex matching any single char of the set which is in the alphabet of the
+    /// DFA, such as a Unicode property class from [`CharSet::property`].
+    pub fn class(chars: CharSet) -> Regex {
+        Regex {
+            expression: Expression::Class(chars),
+        }
+    }
+
+    /// Returns the regex matching the empty string wherever the assertion holds.
+    pub fn assertion(assertion: Assertion) -> Regex {
+        Regex {
+            expression: Expression::Assertion(assertion),
+        }
+    }
+
+    /// Returns the regex matching only the empty string.
+    pub fn epsilon() -> Regex {
+        Regex {
+            expression: Expression::EmptyString,
+        }
+    }
+
+    /// Returns the regex matching no strings at all.
+    pub fn empty() -> Regex {
+        Regex {
+            expression: Expression::EmptySet,
+        }
+    }
+
+    /// Returns the regex matching a string of this regex followed by a string of the other.
+    ///
+    /// If either regex is [`Regex::empty`], so is the result.
+    pub fn then(self, other: Regex) -> Regex {
+        if self.expression == Expression::EmptySet || other.expression == Expression::EmptySet {
+            return Regex::empty();
+        }
+        let mut sub_expressions = Vec::new();
+        for expression in [self.expression, other.expression] {
+            match expression {
+                Expression::Concatenation(inner) => sub_expressions.extend(inner),
+                expression => sub_expressions.push(expression),
+            }
+        }
+        Regex {
+            expression: Expression::Concatenation(sub_expressions),
+        }
+    }
+
+    /// Returns the regex matching a string of either this regex or the other.
+    ///
+    /// If either regex is [`Regex::empty`], the result is the other.
+    pub fn or(self, other: Regex) -> Regex {
+        if self.expression == Expression::EmptySet {
+            return other;
+        }
+        if other.expression == Expression::EmptySet {
+            return self;
+        }
+        let mut sub_expressions = Vec::new();
+        for expression in [self.expression, other.expression] {
+            match expression {
+                Expression::Choice(inner) => sub_expressions.extend(inner),
+                expression => sub_expressions.push(expression),
+            }
+        }
+        Regex {
+            expression: Expression::Choice(sub_expressions),
+        }
+    }
+
+    /// Returns the regex matching any number of strings of this regex, one after another.
+    ///
+    /// The closure of [`Regex::empty`] is [`Regex::epsilon`].
+    pub fn star(self) -> Regex {
+        match self.expression {
+            Expression::EmptySet => Regex::epsilon(),
+            expression => Regex {
+                expression: Expression::Closure(Box::from(expression)),
+            },
+        }
+    }
+
+    /// The expression built.
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// Consumes the regex, returning the expression built.
+    pub fn into_expression(self) -> Expression {
+        self.expression
+    }
+
+    /// Generates a DFA accepting the strings matched by the regex.
+    ///
+    /// The alphabet of the DFA is the given chars along with every char symbol of the regex.
+    pub fn to_dfa(&self, alphabet: &str) -> Result<Dfa, DfaGenerationError> {
+        self.to_dfa_with_options(alphabet, &GenerationOptions::default())
+    }
+
+    /// Generates a DFA accepting the strings matched by the regex, as controlled by the given
+    /// options.
+    ///
+    /// The alphabet of the DFA is the given chars along with every char symbol of the regex.
+    pub fn to_dfa_with_options(
+        &self,
+        alphabet: &str,
+        options: &GenerationOptions,
+    ) -> Result<Dfa, DfaGenerationError> {
+        let mut alphabet: BTreeSet<char> = alphabet.chars().collect();
+        collect_symbols(&self.expression, &mut alphabet);
+        let expression =
+            options.apply(self.expression.clone(), &alphabet.iter().copied().collect());
+        let annotated_expression = annotate_ast(expression)?;
+        Ok(generate_dfa(annotated_expression, alphabet))
+    }
+}
+
+impl From<Expression> for Regex {
+    fn from(expression: Expression) -> Self {
+        Regex { expression }
+    }
+}
+
+/// Adds every char matched by a char leaf of the expression to symbols.
+fn collect_symbols(expression: &Expression, symbols: &mut BTreeSet<char>) {
+    match expression {
+        Expression::Concatenation(sub_expressions) | Expression::Choice(sub_expressions) => {
+            for sub_expression in sub_expressions {
+                collect_symbols(sub_expression, symbols);
+            }
+        }
+        Expression::Closure(sub_expression) => collect_symbols(sub_expression, symbols),
+        Expression::Char(c) => {
+            symbols.insert(*c);
+        }
+        // A class only matches the chars of the alphabet, so never extends it.
+        Expression::Class(_)
+        | Expression::EmptyString
+        | Expression::EmptySet
+        | Expression::Assertion(_) => {}
+    }
+}
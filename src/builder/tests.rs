@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn test_combinators_build_expressions() {
+    let regex = Regex::sym('a')
+        .or(Regex::sym('b'))
+        .or(Regex::epsilon())
+        .star()
+        .then(Regex::sym('a'))
+        .then(Regex::sym('b').then(Regex::sym('c')));
+    let expected_output = Expression::Concatenation(vec![
+        Expression::Closure(Box::from(Expression::Choice(vec![
+            Expression::Char('a'),
+            Expression::Char('b'),
+            Expression::EmptyString,
+        ]))),
+        Expression::Char('a'),
+        Expression::Char('b'),
+        Expression::Char('c'),
+    ]);
+    assert_eq!(regex.expression(), &expected_output);
+}
+
+#[test]
+fn test_to_dfa_matches_string_syntax() {
+    let regex = Regex::sym('a')
+        .or(Regex::sym('b'))
+        .star()
+        .then(Regex::sym('a'))
+        .then(Regex::sym('b'))
+        .then(Regex::sym('b'));
+    let dfa = regex.to_dfa("ab").unwrap();
+    assert_eq!(dfa, crate::generate_dfa("(a|b)*abb", "ab").unwrap());
+}
+
+#[test]
+fn test_reserved_chars_are_symbols() {
+    let regex = Regex::sym('(').then(Regex::sym('*')).star();
+    let dfa = regex.to_dfa("").unwrap();
+    assert_eq!(dfa.alphabet, BTreeSet::from(['(', '*']));
+    assert!(dfa.accepts("(*(*"));
+    assert!(!dfa.accepts("(("));
+}
+
+#[test]
+fn test_empty() {
+    let dfa = Regex::empty().to_dfa("ab").unwrap();
+    assert!(dfa.is_empty());
+    assert_eq!(dfa.alphabet, BTreeSet::from(['a', 'b']));
+    assert_eq!(Regex::empty().then(Regex::sym('a')), Regex::empty());
+    assert_eq!(Regex::sym('a').then(Regex::empty()), Regex::empty());
+    assert_eq!(Regex::empty().or(Regex::sym('a')), Regex::sym('a'));
+    assert_eq!(Regex::empty().star(), Regex::epsilon());
+    assert_eq!(Regex::empty().into_expression(), Expression::EmptySet);
+}
+
+#[test]
+fn test_epsilon() {
+    let dfa = Regex::epsilon().to_dfa("a").unwrap();
+    assert!(dfa.accepts(""));
+    assert!(!dfa.accepts("a"));
+}
+
+#[test]
+fn test_class_does_not_extend_alphabet() {
+    let regex = Regex::class(CharSet::property("Ll").unwrap()).star();
+    let dfa = regex.to_dfa("aB").unwrap();
+    assert_eq!(dfa.alphabet, BTreeSet::from(['B', 'a']));
+    assert!(dfa.accepts("aa"));
+    assert!(!dfa.accepts("aB"));
+}
+
+#[test]
+fn test_assertion() {
+    let regex = Regex::assertion(Assertion::Start)
+        .then(Regex::sym('a'))
+        .then(Regex::assertion(Assertion::WordBoundary))
+        .or(Regex::sym(' '))
+        .star();
+    let dfa = regex.to_dfa("").unwrap();
+    assert!(dfa.accepts("a "));
+    assert!(!dfa.accepts(" a"));
+    assert!(!dfa.accepts("aa"));
+}
@@ -0,0 +1,97 @@
+//! Compiles a [`Dfa`] over chars into a [`ByteDfa`] over the UTF-8 encoding of those chars.
+
+use crate::dfa::Dfa;
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod tests;
+
+/// The number of distinct values of a byte, and thus the width of each row of a [`ByteDfa`]
+/// transition table.
+const BYTE_VALUES: usize = 256;
+
+/// Represents a DFA over bytes, matching the UTF-8 encoding of the strings accepted by some
+/// [`Dfa`].
+///
+/// The states 0..n of the source [`Dfa`] keep their indices. Any further states are
+/// intermediate states, reached part way through the encoding of a multi-byte char, and are
+/// never accepting.
+#[derive(Debug, PartialEq)]
+pub struct ByteDfa {
+    /// The starting state of the DFA.
+    pub(crate) start_state: usize,
+    /// A set of accepting states, as in [`Dfa`].
+    pub(crate) accepting_states: HashSet<usize>,
+    /// A dense transition table, where `transition_table[s][b]` is the state reached from state
+    /// s on byte b. A [`None`] entry implies that the DFA rejects any input which follows that
+    /// path.
+    pub(crate) transition_table: Vec<[Option<usize>; BYTE_VALUES]>,
+}
+
+impl ByteDfa {
+    /// Adds a new state with no outgoing transitions, returning its index.
+    fn add_state(&mut self) -> usize {
+        self.transition_table.push([None; BYTE_VALUES]);
+        self.transition_table.len() - 1
+    }
+
+    /// The number of states in the DFA, including intermediate states.
+    pub fn n_states(&self) -> usize {
+        self.transition_table.len()
+    }
+
+    /// Returns whether the DFA accepts the input bytes.
+    ///
+    /// No decoding takes place, so input which is not valid UTF-8 is simply rejected.
+    pub fn accepts(&self, input: &[u8]) -> bool {
+        let mut state = self.start_state;
+        for byte in input {
+            match self.transition_table[state][*byte as usize] {
+                Some(next_state) => state = next_state,
+                None => return false,
+            }
+        }
+        self.accepting_states.contains(&state)
+    }
+}
+
+impl Dfa {
+    /// Compiles the DFA into a [`ByteDfa`] by expanding each char transition into the UTF-8
+    /// byte sequence of that char.
+    ///
+    /// Transitions on multi-byte chars leaving the same state share intermediate states for
+    /// common prefixes, so the result stays deterministic. States and chars are visited in
+    /// order, so the numbering of intermediate states does not depend on hash ordering.
+    pub fn to_byte_dfa(&self) -> ByteDfa {
+        let mut byte_dfa = ByteDfa {
+            start_state: self.start_state,
+            accepting_states: self.accepting_states.clone(),
+            transition_table: vec![[None; BYTE_VALUES]; self.n_states],
+        };
+        let mut states: Vec<_> = self.transition_function.keys().copied().collect();
+        states.sort_unstable();
+        for state in states {
+            let mut transitions: Vec<_> = self.transition_function[&state].iter().collect();
+            transitions.sort_unstable();
+            for (c, target_state) in transitions {
+                let mut buffer = [0; 4];
+                let (last_byte, prefix) =
+                    c.encode_utf8(&mut buffer).as_bytes().split_last().unwrap();
+                let mut current_state = state;
+                for byte in prefix {
+                    current_state = match byte_dfa.transition_table[current_state][*byte as usize] {
+                        Some(next_state) => next_state,
+                        None => {
+                            let next_state = byte_dfa.add_state();
+                            byte_dfa.transition_table[current_state][*byte as usize] =
+                                Some(next_state);
+                            next_state
+                        }
+                    };
+                }
+                byte_dfa.transition_table[current_state][*last_byte as usize] = Some(*target_state);
+            }
+        }
+        byte_dfa
+    }
+}
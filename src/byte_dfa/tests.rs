@@ -0,0 +1,53 @@
+use crate::generate_dfa;
+
+#[test]
+fn test_ascii_transitions_are_not_expanded() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap();
+    let byte_dfa = dfa.to_byte_dfa();
+    assert_eq!(byte_dfa.n_states(), dfa.n_states);
+    assert!(byte_dfa.accepts(b"abb"));
+    assert!(byte_dfa.accepts(b"babaabb"));
+    assert!(!byte_dfa.accepts(b"ab"));
+    assert!(!byte_dfa.accepts(b""));
+}
+
+#[test]
+fn test_multi_byte_chars() {
+    let dfa = generate_dfa("a⟹(🦀)*", "a⟹🦀").unwrap();
+    let byte_dfa = dfa.to_byte_dfa();
+    assert!(byte_dfa.accepts("a⟹".as_bytes()));
+    assert!(byte_dfa.accepts("a⟹🦀🦀".as_bytes()));
+    assert!(!byte_dfa.accepts("a🦀".as_bytes()));
+    assert!(!byte_dfa.accepts("⟹".as_bytes()));
+}
+
+#[test]
+fn test_truncated_encoding_is_rejected() {
+    let dfa = generate_dfa("🦀", "🦀").unwrap();
+    let byte_dfa = dfa.to_byte_dfa();
+    let encoded = "🦀".as_bytes();
+    for i in 0..encoded.len() {
+        assert!(!byte_dfa.accepts(&encoded[..i]));
+    }
+    assert!(byte_dfa.accepts(encoded));
+}
+
+#[test]
+fn test_invalid_utf8_is_rejected() {
+    let dfa = generate_dfa("a*", "a").unwrap();
+    let byte_dfa = dfa.to_byte_dfa();
+    assert!(!byte_dfa.accepts(&[b'a', 0xff, b'a']));
+    assert!(!byte_dfa.accepts(&[0xc3]));
+}
+
+// "é" is encoded as [0xc3, 0xa9] and "ë" as [0xc3, 0xab], so both should share the
+// intermediate state reached on 0xc3.
+#[test]
+fn test_shared_prefixes_share_intermediate_states() {
+    let dfa = generate_dfa("é|ë", "éë").unwrap();
+    let byte_dfa = dfa.to_byte_dfa();
+    assert_eq!(byte_dfa.n_states(), dfa.n_states + 1);
+    assert!(byte_dfa.accepts("é".as_bytes()));
+    assert!(byte_dfa.accepts("ë".as_bytes()));
+    assert!(!byte_dfa.accepts(&[0xc3, 0xaa]));
+}
@@ -0,0 +1,59 @@
+//! Renumbers the states of a [`Dfa`] canonically, so equal automata compare and print equally.
+
+use crate::dfa::{breadth_first_order, Dfa};
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod tests;
+
+impl Dfa {
+    /// Returns the DFA with its states renumbered canonically.
+    ///
+    /// States are numbered in the order they are discovered by a breadth-first search from the
+    /// start state, following the transitions out of each state in char order. The start state
+    /// is thus always state 0. Two DFAs which differ only in the numbering of their states
+    /// have equal canonical forms. States unreachable from the start state cannot affect the
+    /// language of the DFA, and are dropped.
+    ///
+    /// DFAs produced by [`generate_dfa`](crate::generate_dfa) and [`Dfa::minimize`] are
+    /// already canonical.
+    pub fn canonicalize(&self) -> Dfa {
+        let order = breadth_first_order(self.start_state, |state| {
+            self.sorted_transitions(state)
+                .into_iter()
+                .map(|(_, target_state)| target_state)
+        });
+        let state_indices: HashMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(index, state)| (*state, index))
+            .collect();
+        let mut canonical = Dfa {
+            n_states: order.len(),
+            start_state: 0,
+            accepting_states: HashSet::new(),
+            transition_function: HashMap::new(),
+            alphabet: self.alphabet.clone(),
+        };
+        for (state_index, state) in order.iter().enumerate() {
+            if self.accepting_states.contains(state) {
+                canonical.accepting_states.insert(state_index);
+            }
+            for (c, target_state) in self.sorted_transitions(*state) {
+                canonical
+                    .transition_function
+                    .entry(state_index)
+                    .or_default()
+                    .insert(c, state_indices[&target_state]);
+            }
+        }
+        canonical
+    }
+
+    /// Returns whether the states of the DFA are numbered canonically.
+    ///
+    /// See [`Dfa::canonicalize`].
+    pub fn is_canonical(&self) -> bool {
+        self.canonicalize() == *self
+    }
+}
@@ -0,0 +1,81 @@
+use super::*;
+use crate::generate_dfa;
+use std::collections::BTreeSet;
+
+#[test]
+fn test_canonicalize_renumbers_states() {
+    let dfa = Dfa {
+        n_states: 3,
+        start_state: 2,
+        accepting_states: HashSet::from([0]),
+        transition_function: HashMap::from([
+            (2, HashMap::from([('b', 0), ('a', 1)])),
+            (1, HashMap::from([('a', 0)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    let expected_output = Dfa {
+        n_states: 3,
+        start_state: 0,
+        accepting_states: HashSet::from([2]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([('a', 1), ('b', 2)])),
+            (1, HashMap::from([('a', 2)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert_eq!(dfa.canonicalize(), expected_output);
+    assert!(!dfa.is_canonical());
+    assert!(expected_output.is_canonical());
+}
+
+#[test]
+fn test_canonicalize_drops_unreachable_states() {
+    let dfa = Dfa {
+        n_states: 3,
+        start_state: 0,
+        accepting_states: HashSet::from([1, 2]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([('a', 1)])),
+            (2, HashMap::from([('a', 0)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    let expected_output = Dfa {
+        n_states: 2,
+        start_state: 0,
+        accepting_states: HashSet::from([1]),
+        transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert_eq!(dfa.canonicalize(), expected_output);
+}
+
+#[test]
+fn test_generated_dfas_are_canonical() {
+    let expressions = [
+        "",
+        "a",
+        "(a|b)*abb",
+        "(ab|b)*a(a|b)",
+        "b*|a(ba)*",
+        "((a|b)(a|b))*",
+    ];
+    for expression in expressions {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert!(dfa.is_canonical(), "{}", expression);
+        assert!(dfa.minimize().is_canonical(), "{}", expression);
+    }
+}
+
+// Generation is repeated, as state numbering used to depend on the iteration order of
+// randomly seeded hash maps.
+#[test]
+fn test_generation_is_deterministic() {
+    let expected_output = generate_dfa("(ab|b)*a(a|b)|ba*b", "ab").unwrap();
+    for _ in 0..20 {
+        let output = generate_dfa("(ab|b)*a(a|b)|ba*b", "ab").unwrap();
+        assert_eq!(output, expected_output);
+        assert_eq!(output.to_string(), expected_output.to_string());
+    }
+}
@@ -0,0 +1,168 @@
+//! Represents sets of chars as ranges, so that even very large sets are small to store.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::RangeInclusive;
+
+#[cfg(test)]
+mod tests;
+
+/// Returns the char after c, skipping the surrogate code points, or [`None`] if c is the last
+/// char.
+pub(crate) fn next_char(c: char) -> Option<char> {
+    match c {
+        '\u{D7FF}' => Some('\u{E000}'),
+        c => char::from_u32(c as u32 + 1),
+    }
+}
+
+/// Returns the char before c, skipping the surrogate code points, or [`None`] if c is the
+/// first char.
+pub(crate) fn previous_char(c: char) -> Option<char> {
+    match c {
+        '\u{E000}' => Some('\u{D7FF}'),
+        '\0' => None,
+        c => char::from_u32(c as u32 - 1),
+    }
+}
+
+/// A set of chars, such as a Unicode property or an alphabet, which may be very large.
+///
+/// The set is stored as sorted ranges, none of which overlap or are adjacent, so each set has
+/// exactly one representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CharSet {
+    pub(crate) ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    /// Returns the empty set.
+    pub fn new() -> CharSet {
+        CharSet::default()
+    }
+
+    /// Returns the set of every char.
+    pub fn all() -> CharSet {
+        CharSet {
+            ranges: vec![('\0', char::MAX)],
+        }
+    }
+
+    /// Returns the set of the chars in any of the given ranges, which may overlap.
+    pub fn from_ranges<I>(ranges: I) -> CharSet
+    where
+        I: IntoIterator<Item = RangeInclusive<char>>,
+    {
+        let mut sorted: Vec<(char, char)> = ranges
+            .into_iter()
+            .map(|range| (*range.start(), *range.end()))
+            .filter(|(start, end)| start <= end)
+            .collect();
+        sorted.sort_unstable();
+        let mut merged: Vec<(char, char)> = Vec::new();
+        for (start, end) in sorted {
+            match merged.last_mut() {
+                Some((_, last_end)) if next_char(*last_end).is_none_or(|next| start <= next) => {
+                    *last_end = end.max(*last_end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        CharSet { ranges: merged }
+    }
+
+    /// Returns whether the set contains c.
+    pub fn contains(&self, c: char) -> bool {
+        let i = self.ranges.partition_point(|(_, end)| *end < c);
+        self.ranges.get(i).is_some_and(|(start, _)| *start <= c)
+    }
+
+    /// Returns whether the set contains no chars.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of chars in the set.
+    pub fn len(&self) -> usize {
+        self.ranges().map(|range| range.count()).sum()
+    }
+
+    /// Returns the ranges of the set in ascending order. No two ranges overlap or are
+    /// adjacent.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// Returns the chars of the set in ascending order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges().flatten()
+    }
+
+    /// Returns the set of chars in either set.
+    pub fn union(&self, other: &CharSet) -> CharSet {
+        CharSet::from_ranges(self.ranges().chain(other.ranges()))
+    }
+
+    /// Returns the set of chars in both sets.
+    pub fn intersection(&self, other: &CharSet) -> CharSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (start, end) = self.ranges[i];
+            let (other_start, other_end) = other.ranges[j];
+            let overlap = (start.max(other_start), end.min(other_end));
+            if overlap.0 <= overlap.1 {
+                ranges.push(overlap);
+            }
+            match end < other_end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+        CharSet { ranges }
+    }
+
+    /// Returns the set of chars not in this set.
+    pub fn complement(&self) -> CharSet {
+        let mut ranges = Vec::new();
+        let mut start = Some('\0');
+        for (range_start, range_end) in &self.ranges {
+            if let Some(gap_start) = start {
+                if let Some(gap_end) = previous_char(*range_start).filter(|c| gap_start <= *c) {
+                    ranges.push((gap_start, gap_end));
+                }
+            }
+            start = next_char(*range_end);
+        }
+        if let Some(gap_start) = start {
+            ranges.push((gap_start, char::MAX));
+        }
+        CharSet { ranges }
+    }
+
+    /// Returns the set of chars in this set but not the other.
+    pub fn difference(&self, other: &CharSet) -> CharSet {
+        self.intersection(&other.complement())
+    }
+}
+
+impl FromIterator<char> for CharSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        CharSet::from_ranges(iter.into_iter().map(|c| c..=c))
+    }
+}
+
+/// Writes the set as a bracketed list of its ranges, e.g. `[a-c, x]`.
+impl Display for CharSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ranges: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|(start, end)| match start == end {
+                true => start.escape_debug().to_string(),
+                false => format!("{}-{}", start.escape_debug(), end.escape_debug()),
+            })
+            .collect();
+        write!(f, "[{}]", ranges.join(", "))
+    }
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn test_from_ranges_merges_overlapping_and_adjacent_ranges() {
+    let set = CharSet::from_ranges(['d'..='f', 'a'..='b', 'c'..='c', 'x'..='z', 'y'..='y']);
+    assert_eq!(set.ranges().collect::<Vec<_>>(), ['a'..='f', 'x'..='z']);
+    assert_eq!(set.len(), 9);
+    assert_eq!(set.to_string(), "[a-f, x-z]");
+}
+
+#[test]
+fn test_surrogates_are_skipped() {
+    let set = CharSet::from_ranges(['\u{D000}'..='\u{D7FF}', '\u{E000}'..='\u{E00F}']);
+    assert_eq!(set.ranges.len(), 1);
+    assert_eq!(set.len(), 0x800 + 0x10);
+    assert_eq!(CharSet::all().len(), 0x110000 - 0x800);
+}
+
+#[test]
+fn test_contains() {
+    let set: CharSet = "aceg".chars().collect();
+    for c in "aceg".chars() {
+        assert!(set.contains(c));
+    }
+    for c in "bdfh\0".chars() {
+        assert!(!set.contains(c));
+    }
+    assert!(CharSet::all().contains(char::MAX));
+    assert!(!CharSet::new().contains('a'));
+}
+
+#[test]
+fn test_set_operations() {
+    let left = CharSet::from_ranges(['a'..='m']);
+    let right = CharSet::from_ranges(['h'..='z']);
+    assert_eq!(left.union(&right), CharSet::from_ranges(['a'..='z']));
+    assert_eq!(left.intersection(&right), CharSet::from_ranges(['h'..='m']));
+    assert_eq!(left.difference(&right), CharSet::from_ranges(['a'..='g']));
+    assert_eq!(
+        left.complement(),
+        CharSet::from_ranges(['\0'..='`', 'n'..=char::MAX])
+    );
+    assert_eq!(left.complement().complement(), left);
+    assert_eq!(CharSet::all().complement(), CharSet::new());
+}
@@ -0,0 +1,336 @@
+//! Reports the intermediate results of constructing a [`Dfa`] from an annotated expression.
+
+use crate::annotator::{AnnotatedExpression, AnnotatedExpressionContext, AnnotatedExpressionType};
+use crate::char_set::CharSet;
+use crate::dfa::{calculate_matches_next, generate_dfa_with_states, Dfa, Neighbour};
+use crate::parser::Assertion;
+use crate::trace::Trace;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents what a leaf node of the annotated expression matches.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PositionSymbol {
+    /// Matches a single char.
+    Char(char),
+    /// Matches any single char of a set which is in the alphabet.
+    Class(CharSet),
+    /// Matches the empty string. Such leaves never occur in a state of the DFA.
+    EmptyString,
+    /// Matches no strings at all. Such leaves never occur in a state of the DFA.
+    EmptySet,
+    /// Matches the empty string where the assertion holds, passing on to its followpos
+    /// without consuming a char.
+    Assertion(Assertion),
+    /// Marks the end of the expression. A state of the DFA containing it is accepting.
+    Terminal,
+}
+
+impl Display for PositionSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            PositionSymbol::Char(c) => write!(f, "{:?}", c),
+            PositionSymbol::Class(chars) => write!(f, "{}", chars),
+            PositionSymbol::EmptyString => write!(f, "ε"),
+            PositionSymbol::EmptySet => write!(f, "∅"),
+            PositionSymbol::Assertion(assertion) => write!(f, "{}", assertion),
+            PositionSymbol::Terminal => write!(f, "#"),
+        }
+    }
+}
+
+/// Represents a leaf node, or position, of the annotated expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Position {
+    pub(crate) symbol: PositionSymbol,
+    pub(crate) followpos: BTreeSet<usize>,
+}
+
+impl Position {
+    /// What the position matches.
+    pub fn symbol(&self) -> &PositionSymbol {
+        &self.symbol
+    }
+
+    /// The positions which can match the char following a char matched by this position.
+    pub fn followpos(&self) -> &BTreeSet<usize> {
+        &self.followpos
+    }
+}
+
+/// Represents the kind of a node of the annotated expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NodeKind {
+    /// A leaf matching a single char, with its position.
+    Char(char, usize),
+    /// A leaf matching any single char of a set which is in the alphabet, with its position.
+    Class(CharSet, usize),
+    /// A leaf matching the empty string, with its position.
+    EmptyString(usize),
+    /// A leaf matching no strings, with its position.
+    EmptySet(usize),
+    /// A leaf matching the empty string where the assertion holds, with its position.
+    Assertion(Assertion, usize),
+    /// The leaf marking the end of the expression, with its position.
+    Terminal(usize),
+    /// A Kleene closure of its single child.
+    Closure,
+    /// A concatenation of its children.
+    Concatenation,
+    /// A choice between its children.
+    Choice,
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            NodeKind::Char(c, i) => write!(f, "Char {:?} [{}]", c, i),
+            NodeKind::Class(chars, i) => write!(f, "Class {} [{}]", chars, i),
+            NodeKind::EmptyString(i) => write!(f, "EmptyString [{}]", i),
+            NodeKind::EmptySet(i) => write!(f, "EmptySet [{}]", i),
+            NodeKind::Assertion(assertion, i) => write!(f, "Assertion {} [{}]", assertion, i),
+            NodeKind::Terminal(i) => write!(f, "Terminal [{}]", i),
+            NodeKind::Closure => write!(f, "Closure"),
+            NodeKind::Concatenation => write!(f, "Concatenation"),
+            NodeKind::Choice => write!(f, "Choice"),
+        }
+    }
+}
+
+/// Represents a node of the annotated expression along with its annotations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub(crate) depth: usize,
+    pub(crate) kind: NodeKind,
+    pub(crate) is_nullable: bool,
+    pub(crate) firstpos: BTreeSet<usize>,
+    pub(crate) lastpos: BTreeSet<usize>,
+}
+
+impl Node {
+    /// The depth of the node in the tree, where the root has depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The kind of the node.
+    pub fn kind(&self) -> &NodeKind {
+        &self.kind
+    }
+
+    /// Whether the node matches the empty string.
+    pub fn is_nullable(&self) -> bool {
+        self.is_nullable
+    }
+
+    /// The positions which can match the first char of a string matched by the node.
+    pub fn firstpos(&self) -> &BTreeSet<usize> {
+        &self.firstpos
+    }
+
+    /// The positions which can match the last char of a string matched by the node.
+    pub fn lastpos(&self) -> &BTreeSet<usize> {
+        &self.lastpos
+    }
+}
+
+/// Represents the construction of a [`Dfa`] from a regular expression.
+///
+/// Reports the annotations of every node of the expression, the followpos of every position,
+/// the set of positions and look-behind represented by each state of the resulting DFA, and a
+/// [`Trace`] of how each state was derived.
+#[derive(Debug, PartialEq)]
+pub struct Construction {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) positions: Vec<Position>,
+    pub(crate) state_positions: Vec<BTreeSet<usize>>,
+    pub(crate) state_look_behinds: Vec<Option<Neighbour>>,
+    pub(crate) trace: Trace,
+    pub(crate) dfa: Dfa,
+}
+
+/// Formats a set of positions in ascending order, e.g. "{0, 2}".
+pub(crate) fn format_positions(positions: &BTreeSet<usize>) -> String {
+    let positions: Vec<String> = positions.iter().map(|p| p.to_string()).collect();
+    format!("{{{}}}", positions.join(", "))
+}
+
+/// Formats the positions and look-behind of a state, e.g. "{0, 2} after word".
+pub(crate) fn format_state(positions: &BTreeSet<usize>, look_behind: Option<Neighbour>) -> String {
+    match look_behind {
+        Some(look_behind) => format!("{} after {}", format_positions(positions), look_behind),
+        None => format_positions(positions),
+    }
+}
+
+/// Lists the nodes of the expression in pre-order.
+fn collect_nodes(expression: &AnnotatedExpression, depth: usize, nodes: &mut Vec<Node>) {
+    let sorted = |positions: &HashSet<usize>| positions.iter().copied().collect();
+    let (kind, children): (NodeKind, Vec<&AnnotatedExpression>) = match &expression.expression {
+        AnnotatedExpressionType::Char(c, i) => (NodeKind::Char(*c, *i), vec![]),
+        AnnotatedExpressionType::Class(chars, i) => (NodeKind::Class(chars.clone(), *i), vec![]),
+        AnnotatedExpressionType::EmptyString(i) => (NodeKind::EmptyString(*i), vec![]),
+        AnnotatedExpressionType::EmptySet(i) => (NodeKind::EmptySet(*i), vec![]),
+        AnnotatedExpressionType::Assertion(assertion, i) => {
+            (NodeKind::Assertion(*assertion, *i), vec![])
+        }
+        AnnotatedExpressionType::Terminal(i) => (NodeKind::Terminal(*i), vec![]),
+        AnnotatedExpressionType::Closure(sub_expression) => {
+            (NodeKind::Closure, vec![sub_expression.as_ref()])
+        }
+        AnnotatedExpressionType::Concatenation(sub_expressions) => (
+            NodeKind::Concatenation,
+            sub_expressions.iter().map(|e| e.as_ref()).collect(),
+        ),
+        AnnotatedExpressionType::Choice(sub_expressions) => (
+            NodeKind::Choice,
+            sub_expressions.iter().map(|e| e.as_ref()).collect(),
+        ),
+    };
+    nodes.push(Node {
+        depth,
+        kind,
+        is_nullable: expression.is_nullable,
+        firstpos: sorted(&expression.matches_start),
+        lastpos: sorted(&expression.matches_end),
+    });
+    for child in children {
+        collect_nodes(child, depth + 1, nodes);
+    }
+}
+
+impl Construction {
+    /// Constructs a DFA from an annotated expression, recording each intermediate result.
+    pub(crate) fn new(
+        expression: AnnotatedExpressionContext,
+        alphabet: BTreeSet<char>,
+    ) -> Construction {
+        let mut nodes = Vec::new();
+        collect_nodes(&expression.expression, 0, &mut nodes);
+        let mut matches_next = vec![HashSet::new(); expression.leaves.len()];
+        calculate_matches_next(&expression.expression, &mut matches_next);
+        let positions = expression
+            .leaves
+            .iter()
+            .zip(matches_next)
+            .map(|(leaf, followpos)| Position {
+                symbol: match &leaf.expression {
+                    AnnotatedExpressionType::Char(c, _) => PositionSymbol::Char(*c),
+                    AnnotatedExpressionType::Class(chars, _) => {
+                        PositionSymbol::Class(chars.clone())
+                    }
+                    AnnotatedExpressionType::Terminal(_) => PositionSymbol::Terminal,
+                    AnnotatedExpressionType::EmptySet(_) => PositionSymbol::EmptySet,
+                    AnnotatedExpressionType::Assertion(assertion, _) => {
+                        PositionSymbol::Assertion(*assertion)
+                    }
+                    _ => PositionSymbol::EmptyString,
+                },
+                followpos: followpos.into_iter().collect(),
+            })
+            .collect();
+        let mut trace = Trace::default();
+        let (dfa, states) = generate_dfa_with_states(&expression, alphabet, Some(&mut trace));
+        let (state_positions, state_look_behinds) = states.into_iter().unzip();
+        Construction {
+            nodes,
+            positions,
+            state_positions,
+            state_look_behinds,
+            trace,
+            dfa,
+        }
+    }
+
+    /// The nodes of the annotated expression in pre-order, starting from the root.
+    ///
+    /// The root is always the concatenation of the input expression and the terminal.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The positions of the annotated expression, indexed by position.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// The set of positions represented by a state of the DFA, or [`None`] if there is no
+    /// such state.
+    pub fn state_positions(&self, state: usize) -> Option<&BTreeSet<usize>> {
+        self.state_positions.get(state)
+    }
+
+    /// The neighbour before a state of the DFA, or [`None`] if there is no such state or its
+    /// positions contain no assertion.
+    pub fn state_look_behind(&self, state: usize) -> Option<Neighbour> {
+        self.state_look_behinds.get(state).copied().flatten()
+    }
+
+    /// The ordered log of the steps of the subset construction which produced the DFA.
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// The resulting DFA.
+    pub fn dfa(&self) -> &Dfa {
+        &self.dfa
+    }
+
+    /// Consumes the report, returning the resulting DFA.
+    pub fn into_dfa(self) -> Dfa {
+        self.dfa
+    }
+
+    /// Renders the annotated expression with one node per line, indenting children beneath
+    /// their parent.
+    pub fn render_tree(&self) -> String {
+        let lines: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "{}{}  nullable={} firstpos={} lastpos={}",
+                    "  ".repeat(node.depth),
+                    node.kind,
+                    node.is_nullable,
+                    format_positions(&node.firstpos),
+                    format_positions(&node.lastpos),
+                )
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Renders the followpos table, with a row per position.
+    pub fn render_followpos(&self) -> String {
+        let mut lines = vec![String::from("position  symbol  followpos")];
+        for (i, position) in self.positions.iter().enumerate() {
+            lines.push(format!(
+                "{:>8}  {:>6}  {}",
+                i,
+                position.symbol.to_string(),
+                format_positions(&position.followpos)
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Renders the set of positions represented by each state of the DFA, along with its
+    /// look-behind if it has one.
+    pub fn render_states(&self) -> String {
+        let mut lines = vec![String::from("state  positions")];
+        let states = self.state_positions.iter().zip(&self.state_look_behinds);
+        for (state, (positions, look_behind)) in states.enumerate() {
+            lines.push(format!(
+                "{:>5}  {}",
+                state,
+                format_state(positions, *look_behind)
+            ));
+        }
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::explain;
+
+// Example given in https://tinman.cs.gsu.edu/~raj/4510/f24/RegExp2DFA.pdf.
+#[test]
+fn test_positions() {
+    let construction = explain("(a|b)*abb", "ab").unwrap();
+    let expected_output = vec![
+        (PositionSymbol::Char('a'), BTreeSet::from([0, 1, 2])),
+        (PositionSymbol::Char('b'), BTreeSet::from([0, 1, 2])),
+        (PositionSymbol::Char('a'), BTreeSet::from([3])),
+        (PositionSymbol::Char('b'), BTreeSet::from([4])),
+        (PositionSymbol::Char('b'), BTreeSet::from([5])),
+        (PositionSymbol::Terminal, BTreeSet::new()),
+    ];
+    let output: Vec<(PositionSymbol, BTreeSet<usize>)> = construction
+        .positions()
+        .iter()
+        .map(|position| (position.symbol().clone(), position.followpos().clone()))
+        .collect();
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn test_nodes() {
+    let construction = explain("a*|()", "a").unwrap();
+    let expected_output = vec![
+        Node {
+            depth: 0,
+            kind: NodeKind::Concatenation,
+            is_nullable: false,
+            firstpos: BTreeSet::from([0, 2]),
+            lastpos: BTreeSet::from([2]),
+        },
+        Node {
+            depth: 1,
+            kind: NodeKind::Choice,
+            is_nullable: true,
+            firstpos: BTreeSet::from([0]),
+            lastpos: BTreeSet::from([0]),
+        },
+        Node {
+            depth: 2,
+            kind: NodeKind::Closure,
+            is_nullable: true,
+            firstpos: BTreeSet::from([0]),
+            lastpos: BTreeSet::from([0]),
+        },
+        Node {
+            depth: 3,
+            kind: NodeKind::Char('a', 0),
+            is_nullable: false,
+            firstpos: BTreeSet::from([0]),
+            lastpos: BTreeSet::from([0]),
+        },
+        Node {
+            depth: 2,
+            kind: NodeKind::EmptyString(1),
+            is_nullable: true,
+            firstpos: BTreeSet::new(),
+            lastpos: BTreeSet::new(),
+        },
+        Node {
+            depth: 1,
+            kind: NodeKind::Terminal(2),
+            is_nullable: false,
+            firstpos: BTreeSet::from([2]),
+            lastpos: BTreeSet::from([2]),
+        },
+    ];
+    assert_eq!(construction.nodes(), expected_output);
+}
+
+#[test]
+fn test_state_positions() {
+    let construction = explain("(a|b)*abb", "ab").unwrap();
+    let dfa = construction.dfa();
+    assert_eq!(
+        construction.state_positions(dfa.start_state),
+        Some(&BTreeSet::from([0, 1, 2]))
+    );
+    assert_eq!(construction.state_positions(dfa.n_states), None);
+    let expected_states = BTreeSet::from([
+        BTreeSet::from([0, 1, 2]),
+        BTreeSet::from([0, 1, 2, 3]),
+        BTreeSet::from([0, 1, 2, 4]),
+        BTreeSet::from([0, 1, 2, 5]),
+    ]);
+    let states: BTreeSet<BTreeSet<usize>> = (0..dfa.n_states)
+        .map(|state| construction.state_positions(state).unwrap().clone())
+        .collect();
+    assert_eq!(states, expected_states);
+}
+
+// Every transition on c leads to the union of followpos over the positions matching c.
+#[test]
+fn test_state_positions_are_consistent_with_followpos() {
+    let construction = explain("(ab|b)*a(a|b)", "ab").unwrap();
+    let dfa = construction.dfa();
+    for state in 0..dfa.n_states {
+        for (c, target_state) in dfa.sorted_transitions(state) {
+            let expected_positions: BTreeSet<usize> = construction
+                .state_positions(state)
+                .unwrap()
+                .iter()
+                .map(|p| &construction.positions()[*p])
+                .filter(|position| *position.symbol() == PositionSymbol::Char(c))
+                .flat_map(|position| position.followpos().iter().copied())
+                .collect();
+            assert_eq!(
+                construction.state_positions(target_state),
+                Some(&expected_positions)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_render_followpos() {
+    let construction = explain("a*", "a").unwrap();
+    let expected_output = [
+        "position  symbol  followpos",
+        "       0     'a'  {0, 1}",
+        "       1       #  {}",
+    ]
+    .join("\n");
+    assert_eq!(construction.render_followpos(), expected_output);
+}
+
+#[test]
+fn test_render_states_with_look_behind() {
+    let construction = explain("(a| )\\ba", "a ").unwrap();
+    assert_eq!(construction.state_look_behind(0), None);
+    assert_eq!(construction.state_look_behind(1), Some(Neighbour::NonWord));
+    assert_eq!(construction.state_look_behind(2), Some(Neighbour::Word));
+    let expected_output = [
+        "state  positions",
+        "    0  {0, 1}",
+        "    1  {2} after non-word",
+        "    2  {2} after word",
+        "    3  {4}",
+    ]
+    .join("\n");
+    assert_eq!(construction.render_states(), expected_output);
+}
+
+#[test]
+fn test_into_dfa() {
+    let construction = explain("(a|b)*abb", "ab").unwrap();
+    let dfa = crate::generate_dfa("(a|b)*abb", "ab").unwrap();
+    assert!(construction.into_dfa().is_equivalent(&dfa));
+}
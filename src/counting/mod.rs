@@ -0,0 +1,66 @@
+//! Counts the strings of each length accepted by a [`Dfa`].
+
+use crate::big_count::BigCount;
+use crate::dfa::Dfa;
+
+#[cfg(test)]
+mod tests;
+
+impl Dfa {
+    /// Given the number of strings of some length leading from the start state to each state,
+    /// returns the number of strings one char longer leading to each state.
+    ///
+    /// Only paths through live states are counted, as no other path can be extended to an
+    /// accepted string.
+    fn next_path_counts(&self, path_counts: &[BigCount], live_states: &[bool]) -> Vec<BigCount> {
+        let mut next_path_counts = vec![BigCount::zero(); self.n_states];
+        for (state, count) in path_counts.iter().enumerate() {
+            if count.is_zero() {
+                continue;
+            }
+            for (_, target_state) in self.sorted_transitions(state) {
+                if live_states[target_state] {
+                    next_path_counts[target_state] += count;
+                }
+            }
+        }
+        next_path_counts
+    }
+
+    /// Returns the number of accepted strings with each length from 0 to max_length, indexed
+    /// by length.
+    ///
+    /// The counts are exact, however large. Dividing the count for a length n by |Σ|^n gives
+    /// the proportion of strings of that length which are accepted. The counts are found by
+    /// dynamic programming over the transitions, counting the paths of each length from the
+    /// start state to every state, so take O(max_length × transitions) additions.
+    pub fn density_profile(&self, max_length: usize) -> Vec<BigCount> {
+        let live_states: Vec<bool> = {
+            let live_states = self.live_states();
+            (0..self.n_states)
+                .map(|state| live_states.contains(&state))
+                .collect()
+        };
+        let mut path_counts = vec![BigCount::zero(); self.n_states];
+        if live_states[self.start_state] {
+            path_counts[self.start_state] = BigCount::from(1);
+        }
+        let mut profile = Vec::with_capacity(max_length.saturating_add(1));
+        for length in 0..=max_length {
+            if length > 0 {
+                path_counts = self.next_path_counts(&path_counts, &live_states);
+            }
+            let mut count = BigCount::zero();
+            for state in &self.accepting_states {
+                count += &path_counts[*state];
+            }
+            profile.push(count);
+        }
+        profile
+    }
+
+    /// Returns the exact number of accepted strings with the given length.
+    pub fn count_words(&self, length: usize) -> BigCount {
+        self.density_profile(length).pop().unwrap_or_default()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::big_count::BigCount;
+use crate::generate_dfa;
+
+#[test]
+fn test_count_words() {
+    let cases = [
+        ("(a|b)*abb", 0, 0),
+        ("(a|b)*abb", 3, 1),
+        ("(a|b)*abb", 5, 4),
+        ("(a|b)*", 10, 1024),
+        ("ab|ba|()", 2, 2),
+        ("ab|ba|()", 3, 0),
+    ];
+    for (expression, length, expected_output) in cases {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert_eq!(
+            dfa.count_words(length).to_u64(),
+            Some(expected_output),
+            "{} {}",
+            expression,
+            length
+        );
+    }
+}
+
+#[test]
+fn test_count_words_exceeds_u64() {
+    let dfa = generate_dfa("(a|b|c)*", "abc").unwrap();
+    // 3^50
+    assert_eq!(dfa.count_words(50).to_string(), "717897987691852588770249");
+}
+
+#[test]
+fn test_density_profile() {
+    // Strings with no two consecutive "b"s are counted by the Fibonacci numbers.
+    let dfa = generate_dfa("(a|ba)*(b|())", "ab").unwrap();
+    let profile: Vec<u64> = dfa
+        .density_profile(7)
+        .iter()
+        .map(|count| count.to_u64().unwrap())
+        .collect();
+    assert_eq!(profile, [1, 2, 3, 5, 8, 13, 21, 34]);
+}
+
+#[test]
+fn test_density_profile_matches_words() {
+    let dfa = generate_dfa("a(a|b)*b|b", "ab").unwrap();
+    let profile = dfa.density_profile(6);
+    for (length, count) in profile.iter().enumerate() {
+        let n_words = dfa.words(6).filter(|word| word.len() == length).count();
+        assert_eq!(*count, BigCount::from(n_words as u64), "{}", length);
+    }
+}
@@ -0,0 +1,142 @@
+//! Generates test cases which exercise every part of a [`Dfa`].
+
+use crate::dfa::Dfa;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+#[cfg(test)]
+mod tests;
+
+/// Represents an input to a DFA along with whether the DFA accepts it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestCase {
+    pub(crate) input: String,
+    pub(crate) is_accepted: bool,
+}
+
+impl TestCase {
+    /// The input string.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Whether the DFA accepts the input.
+    pub fn is_accepted(&self) -> bool {
+        self.is_accepted
+    }
+}
+
+impl Dfa {
+    /// Returns the chars of the shortest path from state to the nearest state satisfying
+    /// is_target, along with that state, or [`None`] if there is no such state.
+    ///
+    /// Of the shortest paths, the first in char order is returned.
+    fn shortest_path_to(
+        &self,
+        state: usize,
+        is_target: impl Fn(usize) -> bool,
+    ) -> Option<(Vec<char>, usize)> {
+        let mut predecessors: HashMap<usize, (usize, char)> = HashMap::new();
+        let mut queue = VecDeque::from([state]);
+        let mut visited = vec![false; self.n_states];
+        visited[state] = true;
+        while let Some(current_state) = queue.pop_front() {
+            if is_target(current_state) {
+                let mut path = Vec::new();
+                let mut path_state = current_state;
+                while let Some((predecessor, c)) = predecessors.get(&path_state) {
+                    path.push(*c);
+                    path_state = *predecessor;
+                }
+                path.reverse();
+                return Some((path, current_state));
+            }
+            for (c, target_state) in self.sorted_transitions(current_state) {
+                if !visited[target_state] {
+                    visited[target_state] = true;
+                    predecessors.insert(target_state, (current_state, c));
+                    queue.push_back(target_state);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a set of test cases which together exercise every transition of the DFA and
+    /// end in every state, so both accepting and rejecting each kind of input.
+    ///
+    /// A char of the alphabet with no transition out of a state is treated as a transition
+    /// into an implicit dead state, which rejects every input, so the suite also checks that
+    /// each such char is rejected where it occurs. Only states reachable from the start state
+    /// are covered, as no input can exercise the others.
+    ///
+    /// The set is built greedily and approximates the smallest such set: each input follows
+    /// the shortest path to the nearest transition not yet taken by any input and takes it,
+    /// preferring transitions which do not lead to the dead state, until it can go no
+    /// further. Inputs are then added for any state which no input ends in yet. As every
+    /// input either takes a new transition or ends in a new state, there are at most as many
+    /// inputs as reachable states times the size of the alphabet, plus the reachable states.
+    pub fn test_suite(&self) -> Vec<TestCase> {
+        let reachable_states = self.reachable_states();
+        let mut untaken: BTreeSet<(usize, char)> = reachable_states
+            .iter()
+            .flat_map(|state| self.alphabet.iter().map(move |c| (*state, *c)))
+            .collect();
+        let untaken_from = |untaken: &BTreeSet<(usize, char)>, state: usize| {
+            untaken
+                .range((state, '\0')..=(state, char::MAX))
+                .map(|(_, c)| *c)
+                .collect::<Vec<char>>()
+        };
+        let has_untaken = |untaken: &BTreeSet<(usize, char)>, state: usize| {
+            untaken
+                .range((state, '\0')..=(state, char::MAX))
+                .next()
+                .is_some()
+        };
+        let mut ended_in = vec![false; self.n_states];
+        let mut test_cases = Vec::new();
+        // A state of None is the dead state.
+        let mut add_test_case =
+            |input: Vec<char>, state: Option<usize>, ended_in: &mut Vec<bool>| {
+                if let Some(state) = state {
+                    ended_in[state] = true;
+                }
+                test_cases.push(TestCase {
+                    input: input.into_iter().collect(),
+                    is_accepted: state.is_some_and(|s| self.accepting_states.contains(&s)),
+                });
+            };
+        while !untaken.is_empty() {
+            let mut input = Vec::new();
+            let mut state = Some(self.start_state);
+            while let Some((path, path_state)) =
+                state.and_then(|state| self.shortest_path_to(state, |s| has_untaken(&untaken, s)))
+            {
+                input.extend(path);
+                let chars = untaken_from(&untaken, path_state);
+                // A transition into the dead state ends the input, so it is taken last.
+                let c = chars
+                    .iter()
+                    .copied()
+                    .find(|c| self.next_state(path_state, *c).is_some())
+                    .unwrap_or(chars[0]);
+                untaken.remove(&(path_state, c));
+                input.push(c);
+                state = self.next_state(path_state, c);
+            }
+            add_test_case(input, state, &mut ended_in);
+        }
+        let mut unended: Vec<usize> = reachable_states
+            .into_iter()
+            .filter(|state| !ended_in[*state])
+            .collect();
+        unended.sort_unstable();
+        for state in unended {
+            let (input, _) = self
+                .shortest_path_to(self.start_state, |s| s == state)
+                .unwrap();
+            add_test_case(input, Some(state), &mut ended_in);
+        }
+        test_cases
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::generate_dfa;
+use std::collections::HashSet;
+
+/// Returns the transitions taken, and the states ended in, by the inputs of the test cases,
+/// where a state of [`None`] is the dead state.
+fn exercised(
+    dfa: &Dfa,
+    test_cases: &[TestCase],
+) -> (HashSet<(usize, char)>, HashSet<Option<usize>>) {
+    let mut transitions = HashSet::new();
+    let mut end_states = HashSet::new();
+    for test_case in test_cases {
+        let mut state = Some(dfa.start_state);
+        for c in test_case.input().chars() {
+            let current_state = state.expect("input continues past the dead state");
+            transitions.insert((current_state, c));
+            state = dfa.next_state(current_state, c);
+        }
+        end_states.insert(state);
+        assert_eq!(dfa.accepts(test_case.input()), test_case.is_accepted());
+    }
+    (transitions, end_states)
+}
+
+/// Returns the inputs of the test suite of the DFA, with whether each is accepted.
+fn inputs(dfa: &Dfa) -> Vec<(String, bool)> {
+    dfa.test_suite()
+        .iter()
+        .map(|t| (String::from(t.input()), t.is_accepted()))
+        .collect()
+}
+
+#[test]
+fn test_suite_covers_every_transition_and_state() {
+    for expression in ["(a|b)*abb", "ab|ba|()", "a(b|c)*a|c", "(a|b)(a|b)(a|b)"] {
+        let dfa = generate_dfa(expression, "abc").unwrap();
+        let test_cases = dfa.test_suite();
+        let (transitions, end_states) = exercised(&dfa, &test_cases);
+        assert_eq!(
+            transitions.len(),
+            dfa.n_states * dfa.alphabet.len(),
+            "{}",
+            expression
+        );
+        let n_transitions: usize = dfa.transition_function.values().map(|t| t.len()).sum();
+        let has_dead_state = n_transitions < dfa.n_states * dfa.alphabet.len();
+        assert_eq!(
+            end_states.len(),
+            dfa.n_states + usize::from(has_dead_state),
+            "{}",
+            expression
+        );
+        assert!(test_cases.iter().any(|t| t.is_accepted()), "{}", expression);
+        assert!(
+            test_cases.iter().any(|t| !t.is_accepted()),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_suite_of_single_state() {
+    let dfa = generate_dfa("a*", "a").unwrap();
+    let expected_output = vec![TestCase {
+        input: String::from("a"),
+        is_accepted: true,
+    }];
+    assert_eq!(dfa.test_suite(), expected_output);
+}
+
+#[test]
+fn test_suite_rejects_chars_without_transitions() {
+    let dfa = generate_dfa("a*", "ab").unwrap();
+    let expected_output = [(String::from("ab"), false), (String::from(""), true)];
+    assert_eq!(inputs(&dfa), expected_output);
+}
+
+#[test]
+fn test_suite_follows_untaken_transitions() {
+    // The first input takes every transition of the chain before leaving it for the dead
+    // state. Every other char rejected from each state needs its own input, and the last
+    // inputs end in the states which no input has ended in.
+    let dfa = generate_dfa("abc", "abc").unwrap();
+    let expected_output = [
+        ("abca", false),
+        ("b", false),
+        ("c", false),
+        ("aa", false),
+        ("ac", false),
+        ("aba", false),
+        ("abb", false),
+        ("abcb", false),
+        ("abcc", false),
+        ("", false),
+        ("a", false),
+        ("ab", false),
+        ("abc", true),
+    ];
+    let expected_output: Vec<(String, bool)> = expected_output
+        .iter()
+        .map(|(input, is_accepted)| (String::from(*input), *is_accepted))
+        .collect();
+    assert_eq!(inputs(&dfa), expected_output);
+}
@@ -1,7 +1,13 @@
 //! Generates a DFA from [`AnnotatedExpressionContext`].
 
 use crate::annotator::{AnnotatedExpression, AnnotatedExpressionContext, AnnotatedExpressionType};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use crate::char_set::CharSet;
+use crate::parser::Assertion;
+use crate::trace::{Trace, TraceEvent};
+use crate::unicode::word_chars;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::fmt::Formatter;
 
 #[cfg(test)]
 mod tests;
@@ -20,11 +26,127 @@ pub struct Dfa {
     /// exist for state n, char c then this implies that the DFA rejects any word
     /// which follows that path.
     pub(crate) transition_function: HashMap<usize, HashMap<char, usize>>,
+    /// The alphabet the DFA is defined over. Every char labelling a transition is in the
+    /// alphabet, but a char in the alphabet need not label any transition.
+    pub(crate) alphabet: BTreeSet<char>,
+}
+
+impl Dfa {
+    /// Returns the state reached from state on c, or [`None`] if there is no such transition.
+    pub(crate) fn next_state(&self, state: usize, c: char) -> Option<usize> {
+        self.transition_function
+            .get(&state)
+            .and_then(|transitions| transitions.get(&c))
+            .copied()
+    }
+
+    /// Returns the transitions out of state, ordered by char.
+    pub(crate) fn sorted_transitions(&self, state: usize) -> Vec<(char, usize)> {
+        let mut transitions: Vec<(char, usize)> = self
+            .transition_function
+            .get(&state)
+            .map(|transitions| transitions.iter().map(|(c, t)| (*c, *t)).collect())
+            .unwrap_or_default();
+        transitions.sort_unstable();
+        transitions
+    }
+
+    /// Returns every char which labels some transition of the DFA.
+    pub(crate) fn symbols(&self) -> BTreeSet<char> {
+        self.transition_function
+            .values()
+            .flat_map(|transitions| transitions.keys().copied())
+            .collect()
+    }
+
+    /// Returns the states which can be reached from the start state.
+    pub(crate) fn reachable_states(&self) -> HashSet<usize> {
+        breadth_first_order(self.start_state, |state| {
+            self.sorted_transitions(state)
+                .into_iter()
+                .map(|(_, target_state)| target_state)
+        })
+        .into_iter()
+        .collect()
+    }
+
+    /// Returns the states from which some accepting state can be reached.
+    ///
+    /// Any input reaching a state outside of this set is rejected, no matter how it continues.
+    pub(crate) fn live_states(&self) -> HashSet<usize> {
+        live_states(
+            &self.accepting_states,
+            self.transition_function
+                .iter()
+                .flat_map(|(state, transitions)| {
+                    transitions
+                        .values()
+                        .map(move |target_state| (*state, *target_state))
+                }),
+        )
+    }
+
+    /// Returns the states which are both reachable and live, in ascending order. These are the
+    /// only states which can take part in accepting a string.
+    pub(crate) fn useful_states(&self) -> Vec<usize> {
+        useful_states(&self.reachable_states(), &self.live_states())
+    }
+}
+
+/// Returns the states reachable from start_state in the order a breadth-first search
+/// discovers them, where successors lists the states reached by the transitions out of a
+/// state, in the order they are followed.
+pub(crate) fn breadth_first_order<F, I>(start_state: usize, successors: F) -> Vec<usize>
+where
+    F: Fn(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let mut order = vec![start_state];
+    let mut visited = HashSet::from([start_state]);
+    let mut next = 0;
+    while let Some(state) = order.get(next).copied() {
+        for target_state in successors(state) {
+            if visited.insert(target_state) {
+                order.push(target_state);
+            }
+        }
+        next += 1;
+    }
+    order
+}
+
+/// Returns the states from which some accepting state can be reached by the transitions, each
+/// given as a state and the state it reaches.
+pub(crate) fn live_states<I>(accepting_states: &HashSet<usize>, transitions: I) -> HashSet<usize>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (state, target_state) in transitions {
+        predecessors.entry(target_state).or_default().push(state);
+    }
+    let mut live = accepting_states.clone();
+    let mut queue: VecDeque<usize> = live.iter().copied().collect();
+    while let Some(state) = queue.pop_front() {
+        for predecessor in predecessors.get(&state).into_iter().flatten() {
+            if live.insert(*predecessor) {
+                queue.push_back(*predecessor);
+            }
+        }
+    }
+    live
+}
+
+/// Returns the states which are both reachable and live, in ascending order.
+pub(crate) fn useful_states(reachable: &HashSet<usize>, live: &HashSet<usize>) -> Vec<usize> {
+    let mut useful_states: Vec<usize> = reachable.intersection(live).copied().collect();
+    useful_states.sort_unstable();
+    useful_states
 }
 
 /// calculate_matches_next(e)[i] is a set of the leaf nodes which will match the first
 /// character of the string remaining after matching node i.
-fn calculate_matches_next(
+pub(crate) fn calculate_matches_next(
     expression: &AnnotatedExpression,
     matches_next: &mut Vec<HashSet<usize>>,
 ) {
@@ -60,68 +182,264 @@ fn calculate_matches_next(
     }
 }
 
+/// The kind of char on one side of a position in the input, which decides whether each
+/// [`Assertion`] holds at that position.
+///
+/// A state of a DFA generated from an expression with assertions records the neighbour before
+/// it as its look-behind, as reported by [`TraceEvent::StateDiscovered`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Neighbour {
+    /// The start or the end of the input, rather than a char.
+    Edge,
+    /// A word char.
+    Word,
+    /// Any other char.
+    NonWord,
+}
+
+impl Display for Neighbour {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Neighbour::Edge => write!(f, "edge"),
+            Neighbour::Word => write!(f, "word"),
+            Neighbour::NonWord => write!(f, "non-word"),
+        }
+    }
+}
+
+impl Neighbour {
+    /// Returns the neighbour which c is, given the set of word chars.
+    pub(crate) fn of(c: char, word_chars: &CharSet) -> Neighbour {
+        match word_chars.contains(c) {
+            true => Neighbour::Word,
+            false => Neighbour::NonWord,
+        }
+    }
+}
+
+/// Returns whether the assertion holds at a position between the previous and next neighbours.
+pub(crate) fn holds(assertion: Assertion, previous: Neighbour, next: Neighbour) -> bool {
+    let is_boundary = (previous == Neighbour::Word) != (next == Neighbour::Word);
+    match assertion {
+        Assertion::Start => previous == Neighbour::Edge,
+        Assertion::End => next == Neighbour::Edge,
+        Assertion::WordBoundary => is_boundary,
+        Assertion::NotWordBoundary => !is_boundary,
+    }
+}
+
+/// Returns the positions along with those reached by passing through the assertion positions
+/// among them for which holds returns true. Assertions consume no chars, so this is repeated
+/// for any assertion positions reached.
+pub(crate) fn pass_assertions<F>(
+    expression: &AnnotatedExpressionContext,
+    matches_next: &[HashSet<usize>],
+    positions: &BTreeSet<usize>,
+    holds: F,
+) -> BTreeSet<usize>
+where
+    F: Fn(Assertion) -> bool,
+{
+    let mut passed = positions.clone();
+    let mut unvisited: Vec<usize> = positions.iter().copied().collect();
+    while let Some(position) = unvisited.pop() {
+        if let AnnotatedExpressionType::Assertion(assertion, i) =
+            &expression.leaves[position].expression
+        {
+            if holds(*assertion) {
+                for next_position in &matches_next[*i] {
+                    if passed.insert(*next_position) {
+                        unvisited.push(*next_position);
+                    }
+                }
+            }
+        }
+    }
+    passed
+}
+
+/// Returns the look-behind to record in a state of the positions, reached after the previous
+/// neighbour.
+///
+/// Only assertions depend on the previous neighbour, so the look-behind is [`None`] unless the
+/// positions contain an assertion. Expressions without assertions thus give the same states
+/// as if there were no look-behind.
+pub(crate) fn look_behind(
+    expression: &AnnotatedExpressionContext,
+    positions: &BTreeSet<usize>,
+    previous: Neighbour,
+) -> Option<Neighbour> {
+    positions
+        .iter()
+        .any(|position| {
+            matches!(
+                expression.leaves[*position].expression,
+                AnnotatedExpressionType::Assertion(_, _)
+            )
+        })
+        .then_some(previous)
+}
+
+/// A state of the subset construction: the positions it represents along with its look-behind.
+pub(crate) type PositionState = (BTreeSet<usize>, Option<Neighbour>);
+
 /// Generates a DFA from an input annotated expression with leaf context.
-pub(crate) fn generate_dfa(expression: AnnotatedExpressionContext) -> Dfa {
+pub(crate) fn generate_dfa(
+    expression: AnnotatedExpressionContext,
+    alphabet: BTreeSet<char>,
+) -> Dfa {
+    generate_dfa_with_states(&expression, alphabet, None).0
+}
+
+/// Records an event of the construction if a trace was requested.
+fn record(trace: &mut Option<&mut Trace>, event: TraceEvent) {
+    if let Some(trace) = trace {
+        trace.events.push(event);
+    }
+}
+
+/// Generates a DFA from an input annotated expression with leaf context, along with the set
+/// of leaf nodes and the look-behind represented by each state of the DFA, indexed by state.
+///
+/// Each state also records the neighbour before it as a look-behind, if it has assertion
+/// positions, so the same positions may be represented by several states. Assertions are
+/// passed through on each transition, once the next char is known, and when deciding whether
+/// a state is accepting, at the end of the input.
+///
+/// If a trace is given, every step of the construction is recorded to it in order.
+pub(crate) fn generate_dfa_with_states(
+    expression: &AnnotatedExpressionContext,
+    alphabet: BTreeSet<char>,
+    mut trace: Option<&mut Trace>,
+) -> (Dfa, Vec<PositionState>) {
     let mut matches_next = vec![HashSet::<usize>::new(); expression.leaves.len()];
-    let mut unmarked_states_map = HashMap::new();
-    let mut marked_states_map = HashMap::new();
+    let mut unmarked_states = VecDeque::new();
+    let mut state_indices = HashMap::new();
     let mut dfa = Dfa {
         n_states: 0,
         start_state: 0,
         accepting_states: HashSet::new(),
         transition_function: HashMap::new(),
+        alphabet,
     };
     calculate_matches_next(&expression.expression, &mut matches_next);
-    let initial_state = BTreeSet::from_iter(expression.expression.matches_start.iter().copied());
+    let word_chars = word_chars();
+    let terminal_position = expression.leaves.len() - 1;
+    // Passes the assertions which hold between the look-behind of a state and next.
+    let pass = |(positions, previous): &PositionState, next| {
+        pass_assertions(expression, &matches_next, positions, |assertion| {
+            previous.is_some_and(|previous| holds(assertion, previous, next))
+        })
+    };
+    let initial_positions =
+        BTreeSet::from_iter(expression.expression.matches_start.iter().copied());
+    let initial_look_behind = look_behind(expression, &initial_positions, Neighbour::Edge);
+    let initial_state = (initial_positions, initial_look_behind);
     dfa.n_states = 1;
-    if initial_state.contains(&(expression.leaves.len() - 1)) {
+    record(
+        &mut trace,
+        TraceEvent::StateDiscovered {
+            state: 0,
+            positions: initial_state.0.clone(),
+            look_behind: initial_state.1,
+        },
+    );
+    if pass(&initial_state, Neighbour::Edge).contains(&terminal_position) {
         dfa.accepting_states.insert(0);
+        record(
+            &mut trace,
+            TraceEvent::AcceptingStateIdentified { state: 0 },
+        );
     }
-    unmarked_states_map.insert(initial_state, 0);
-    let mut next_state_index = 1;
-    while !unmarked_states_map.is_empty() {
-        let unmarked_state = unmarked_states_map.keys().next().unwrap().clone();
-        let unmarked_state_index = unmarked_states_map.remove(&unmarked_state).unwrap();
-        marked_states_map.insert(unmarked_state.clone(), unmarked_state_index);
-        let mut grouped_by_char: HashMap<char, Vec<usize>> = HashMap::new();
-        for leaf_index in unmarked_state {
-            if let AnnotatedExpressionType::Char(c, i) = &expression.leaves[leaf_index].expression {
-                for k in &matches_next[*i] {
-                    grouped_by_char.entry(*c).or_default().push(*k);
+    state_indices.insert(initial_state.clone(), 0);
+    unmarked_states.push_back((initial_state, 0));
+    // States are marked in the order they are discovered, and their transitions are added in
+    // char order, so states are numbered by a breadth-first search in char order.
+    while let Some((unmarked_state, unmarked_state_index)) = unmarked_states.pop_front() {
+        record(
+            &mut trace,
+            TraceEvent::StateMarked {
+                state: unmarked_state_index,
+            },
+        );
+        let mut grouped_by_char: BTreeMap<char, BTreeSet<usize>> = BTreeMap::new();
+        // The assertions passed before a char depend on whether it is a word char.
+        for next in [Neighbour::Word, Neighbour::NonWord] {
+            let is_next = |c: &char| Neighbour::of(*c, word_chars) == next;
+            for leaf_index in pass(&unmarked_state, next) {
+                match &expression.leaves[leaf_index].expression {
+                    AnnotatedExpressionType::Char(c, i) if is_next(c) => {
+                        grouped_by_char
+                            .entry(*c)
+                            .or_default()
+                            .extend(matches_next[*i].iter().copied());
+                    }
+                    AnnotatedExpressionType::Class(chars, i) => {
+                        // The class is intersected with the alphabet, which may be far smaller.
+                        let chars = dfa.alphabet.iter().filter(|c| chars.contains(**c));
+                        for c in chars.filter(|c| is_next(c)) {
+                            grouped_by_char
+                                .entry(*c)
+                                .or_default()
+                                .extend(matches_next[*i].iter().copied());
+                        }
+                    }
+                    _ => (),
                 }
             }
         }
-        for (c, char_leaves) in grouped_by_char {
-            let mut target_state = BTreeSet::new();
-            let target_state_index;
-            for leaf in char_leaves {
-                target_state.insert(leaf);
-            }
-            if target_state.is_empty() {
+        for (c, target_positions) in grouped_by_char {
+            if target_positions.is_empty() {
                 continue;
             }
-            if !unmarked_states_map.contains_key(&target_state)
-                && !marked_states_map.contains_key(&target_state)
+            let target_look_behind =
+                look_behind(expression, &target_positions, Neighbour::of(c, word_chars));
+            let target_state = (target_positions, target_look_behind);
+            let target_state_index = match state_indices.get(&target_state) {
+                Some(target_state_index) => *target_state_index,
+                None => {
+                    let target_state_index = dfa.n_states;
+                    dfa.n_states += 1;
+                    record(
+                        &mut trace,
+                        TraceEvent::StateDiscovered {
+                            state: target_state_index,
+                            positions: target_state.0.clone(),
+                            look_behind: target_state.1,
+                        },
+                    );
+                    state_indices.insert(target_state.clone(), target_state_index);
+                    unmarked_states.push_back((target_state.clone(), target_state_index));
+                    target_state_index
+                }
+            };
+            if pass(&target_state, Neighbour::Edge).contains(&terminal_position)
+                && dfa.accepting_states.insert(target_state_index)
             {
-                unmarked_states_map
-                    .entry(target_state.clone())
-                    .or_insert(next_state_index);
-                dfa.n_states += 1;
-                target_state_index = next_state_index;
-                next_state_index += 1;
-            } else if unmarked_states_map.contains_key(&target_state) {
-                target_state_index = unmarked_states_map[&target_state];
-            } else {
-                target_state_index = marked_states_map[&target_state];
-            }
-            if target_state.contains(&(expression.leaves.len() - 1)) {
-                dfa.accepting_states.insert(target_state_index);
+                record(
+                    &mut trace,
+                    TraceEvent::AcceptingStateIdentified {
+                        state: target_state_index,
+                    },
+                );
             }
             dfa.transition_function
                 .entry(unmarked_state_index)
                 .or_default()
                 .insert(c, target_state_index);
+            record(
+                &mut trace,
+                TraceEvent::TransitionAdded {
+                    from: unmarked_state_index,
+                    c,
+                    to: target_state_index,
+                },
+            );
         }
     }
-    dfa
+    let mut states = vec![(BTreeSet::new(), None); dfa.n_states];
+    for (state, state_index) in state_indices {
+        states[state_index] = state;
+    }
+    (dfa, states)
 }
@@ -1,6 +1,6 @@
 use super::*;
 use crate::annotator::annotate_ast;
-use crate::parser::Expression;
+use crate::parser::{Assertion, Expression};
 
 // Example given in https://tinman.cs.gsu.edu/~raj/4510/f24/RegExp2DFA.pdf.
 //
@@ -52,9 +52,10 @@ fn test_generates_dfa() {
             (2, HashMap::from([('a', 1), ('b', 3)])),
             (3, HashMap::from([('a', 1), ('b', 0)])),
         ]),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
 
@@ -69,9 +70,10 @@ fn test_char_expression_dfa() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
 
@@ -83,9 +85,10 @@ fn test_empty_string_expression_dfa() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::new(),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
 
@@ -100,9 +103,10 @@ fn test_concatenation_expression_dfa() {
             (0, HashMap::from([('a', 1)])),
             (1, HashMap::from([('b', 2)])),
         ]),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
 
@@ -114,9 +118,10 @@ fn test_not_nullable_choice_expression_dfa() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1), ('b', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
 
@@ -132,8 +137,152 @@ fn test_nullable_choice_expression_dfa() {
         start_state: 0,
         accepting_states: HashSet::from([0, 1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1), ('b', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
     };
     let annotated_input = annotate_ast(input).unwrap();
-    let output = generate_dfa(annotated_input);
+    let output = generate_dfa(annotated_input, BTreeSet::from(['a', 'b']));
     assert_eq!(output, expected_output);
 }
+
+/// Returns the ends of the matches of expression in input starting at start, where assertions
+/// look at the chars either side of each position. A reference for the DFA semantics.
+fn match_ends(expression: &Expression, input: &[char], start: usize) -> BTreeSet<usize> {
+    let word_chars = word_chars();
+    let neighbour = |i: Option<&char>| match i {
+        None => Neighbour::Edge,
+        Some(c) => Neighbour::of(*c, word_chars),
+    };
+    match expression {
+        Expression::Char(c) => match input.get(start) == Some(c) {
+            true => BTreeSet::from([start + 1]),
+            false => BTreeSet::new(),
+        },
+        Expression::Class(chars) => match input.get(start).is_some_and(|c| chars.contains(*c)) {
+            true => BTreeSet::from([start + 1]),
+            false => BTreeSet::new(),
+        },
+        Expression::EmptyString => BTreeSet::from([start]),
+        Expression::EmptySet => BTreeSet::new(),
+        Expression::Assertion(assertion) => {
+            let previous = neighbour(start.checked_sub(1).and_then(|i| input.get(i)));
+            match holds(*assertion, previous, neighbour(input.get(start))) {
+                true => BTreeSet::from([start]),
+                false => BTreeSet::new(),
+            }
+        }
+        Expression::Concatenation(sub_expressions) => {
+            sub_expressions
+                .iter()
+                .fold(BTreeSet::from([start]), |starts, sub_expression| {
+                    starts
+                        .into_iter()
+                        .flat_map(|start| match_ends(sub_expression, input, start))
+                        .collect()
+                })
+        }
+        Expression::Choice(sub_expressions) => sub_expressions
+            .iter()
+            .flat_map(|sub_expression| match_ends(sub_expression, input, start))
+            .collect(),
+        Expression::Closure(sub_expression) => {
+            let mut ends = BTreeSet::from([start]);
+            let mut unvisited = vec![start];
+            while let Some(start) = unvisited.pop() {
+                for end in match_ends(sub_expression, input, start) {
+                    if ends.insert(end) {
+                        unvisited.push(end);
+                    }
+                }
+            }
+            ends
+        }
+    }
+}
+
+#[test]
+fn test_holds() {
+    use Neighbour::{Edge, NonWord, Word};
+    assert!(holds(Assertion::Start, Edge, Word));
+    assert!(!holds(Assertion::Start, Word, Edge));
+    assert!(holds(Assertion::End, NonWord, Edge));
+    assert!(holds(Assertion::WordBoundary, Edge, Word));
+    assert!(holds(Assertion::WordBoundary, Word, NonWord));
+    assert!(!holds(Assertion::WordBoundary, Edge, NonWord));
+    assert!(holds(Assertion::NotWordBoundary, Edge, Edge));
+    assert!(!holds(Assertion::NotWordBoundary, NonWord, Word));
+}
+
+#[test]
+fn test_assertions_match_reference() {
+    let assertion = |assertion| Expression::Assertion(assertion);
+    let word_boundary = || assertion(Assertion::WordBoundary);
+    let cases = [
+        // ^a|b$
+        Expression::Choice(vec![
+            Expression::Concatenation(vec![assertion(Assertion::Start), Expression::Char('a')]),
+            Expression::Concatenation(vec![Expression::Char('b'), assertion(Assertion::End)]),
+        ]),
+        // (\ba\b| )*
+        Expression::Closure(Box::from(Expression::Choice(vec![
+            Expression::Concatenation(vec![
+                word_boundary(),
+                Expression::Char('a'),
+                word_boundary(),
+            ]),
+            Expression::Char(' '),
+        ]))),
+        // (a|\B| )*\Bb
+        Expression::Concatenation(vec![
+            Expression::Closure(Box::from(Expression::Choice(vec![
+                Expression::Char('a'),
+                assertion(Assertion::NotWordBoundary),
+                Expression::Char(' '),
+            ]))),
+            assertion(Assertion::NotWordBoundary),
+            Expression::Char('b'),
+        ]),
+        // (^|a)*(\b|$)(b| )*
+        Expression::Concatenation(vec![
+            Expression::Closure(Box::from(Expression::Choice(vec![
+                assertion(Assertion::Start),
+                Expression::Char('a'),
+            ]))),
+            Expression::Choice(vec![word_boundary(), assertion(Assertion::End)]),
+            Expression::Closure(Box::from(Expression::Choice(vec![
+                Expression::Char('b'),
+                Expression::Char(' '),
+            ]))),
+        ]),
+    ];
+    let alphabet = ['a', 'b', ' '];
+    for expression in cases {
+        let dfa = generate_dfa(
+            annotate_ast(expression.clone()).unwrap(),
+            alphabet.into_iter().collect(),
+        );
+        let mut inputs = vec![Vec::new()];
+        for _ in 0..5 {
+            for input in &inputs {
+                let expected = match_ends(&expression, input, 0).contains(&input.len());
+                let string: String = input.iter().collect();
+                assert_eq!(
+                    dfa.accepts(&string),
+                    expected,
+                    "{} on {:?}",
+                    expression,
+                    string
+                );
+            }
+            inputs = inputs
+                .iter()
+                .flat_map(|input| {
+                    alphabet.into_iter().map(move |c| {
+                        let mut input = input.clone();
+                        input.push(c);
+                        input
+                    })
+                })
+                .collect();
+        }
+    }
+}
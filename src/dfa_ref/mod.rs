@@ -0,0 +1,93 @@
+//! Matches input with a [`DfaRef`], borrowed from bytes without decoding them.
+
+use crate::binary::{DfaDecodingError, Layout};
+use crate::dfa::Dfa;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents a DFA borrowed from bytes in the format written by [`Dfa::to_bytes`], such as
+/// those of an `include_bytes!` blob or a memory-mapped file.
+///
+/// The bytes are validated once when the DFA is created, in O(A + N + T log A) time for A
+/// chars, N states and T transitions, and are then matched against as they are, so nothing is
+/// allocated. The transition on a char is
+/// found by binary search over the transitions of the current state, which are stored in
+/// ascending order of char.
+#[derive(Clone, Copy, Debug)]
+pub struct DfaRef<'a> {
+    bytes: &'a [u8],
+    layout: Layout,
+}
+
+impl<'a> DfaRef<'a> {
+    /// Borrows a DFA from bytes written by [`Dfa::to_bytes`], after checking that they are
+    /// well formed, as [`Dfa::from_bytes`] does.
+    pub fn new(bytes: &'a [u8]) -> Result<DfaRef<'a>, DfaDecodingError> {
+        let layout = Layout::validate(bytes)?;
+        Ok(DfaRef { bytes, layout })
+    }
+
+    /// Returns the bytes the DFA is borrowed from.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The number of states in the DFA. The states of the DFA are thus 0..n_states.
+    pub fn n_states(&self) -> usize {
+        self.layout.n_states
+    }
+
+    /// The starting state of the DFA.
+    pub fn start_state(&self) -> usize {
+        self.layout.start_state
+    }
+
+    /// Returns whether state is accepting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if state is not less than the number of states.
+    pub fn is_accepting(&self, state: usize) -> bool {
+        assert!(state < self.layout.n_states, "state out of range");
+        self.layout.is_accepting(self.bytes, state)
+    }
+
+    /// Returns the state reached from state on c, or [`None`] if there is no such transition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if state is not less than the number of states.
+    pub fn next_state(&self, state: usize, c: char) -> Option<usize> {
+        assert!(state < self.layout.n_states, "state out of range");
+        let mut transitions = self.layout.transition_range(self.bytes, state);
+        while !transitions.is_empty() {
+            let middle = transitions.start + transitions.len() / 2;
+            let (transition_char, target_state) = self.layout.transition(self.bytes, middle);
+            match transition_char.cmp(&(c as u32)) {
+                std::cmp::Ordering::Less => transitions.start = middle + 1,
+                std::cmp::Ordering::Greater => transitions.end = middle,
+                std::cmp::Ordering::Equal => return Some(target_state),
+            }
+        }
+        None
+    }
+
+    /// Returns whether the DFA accepts the input string.
+    pub fn accepts(&self, input: &str) -> bool {
+        let mut state = self.layout.start_state;
+        for c in input.chars() {
+            match self.next_state(state, c) {
+                Some(next_state) => state = next_state,
+                None => return false,
+            }
+        }
+        self.layout.is_accepting(self.bytes, state)
+    }
+
+    /// Decodes the borrowed DFA into an owned [`Dfa`].
+    pub fn to_dfa(&self) -> Dfa {
+        // The bytes were validated when borrowed, so decoding them again cannot fail.
+        Dfa::from_bytes(self.bytes).unwrap()
+    }
+}
@@ -0,0 +1,58 @@
+use super::*;
+use crate::{generate_dfa, DfaDecodingError};
+
+#[test]
+fn test_matches_as_dfa() {
+    let cases = [
+        ("(a|b)*abb", "ab"),
+        ("", "ab"),
+        ("∅", "ab"),
+        ("a⟹(🦀)*", "a⟹🦀"),
+        ("(\\ba|b\\B| )*$", "ab "),
+    ];
+    for (input_expression, alphabet) in cases {
+        let dfa = generate_dfa(input_expression, alphabet).unwrap();
+        let bytes = dfa.to_bytes();
+        let dfa_ref = DfaRef::new(&bytes).unwrap();
+        assert_eq!(dfa_ref.n_states(), dfa.n_states);
+        assert_eq!(dfa_ref.start_state(), dfa.start_state);
+        let mut inputs = vec![String::new()];
+        for _ in 0..4 {
+            for input in &inputs {
+                assert_eq!(dfa_ref.accepts(input), dfa.accepts(input), "{}", input);
+            }
+            inputs = inputs
+                .iter()
+                .flat_map(|input| alphabet.chars().map(move |c| format!("{}{}", input, c)))
+                .collect();
+        }
+        assert_eq!(dfa_ref.to_dfa(), dfa);
+    }
+}
+
+#[test]
+fn test_transitions() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap();
+    let bytes = dfa.to_bytes();
+    let dfa_ref = DfaRef::new(&bytes).unwrap();
+    for state in 0..dfa.n_states {
+        assert_eq!(
+            dfa_ref.is_accepting(state),
+            dfa.accepting_states.contains(&state)
+        );
+        for c in ['a', 'b', 'c'] {
+            assert_eq!(dfa_ref.next_state(state, c), dfa.next_state(state, c));
+        }
+    }
+    assert!(std::ptr::eq(dfa_ref.as_bytes(), bytes.as_slice()));
+}
+
+#[test]
+fn test_invalid_bytes() {
+    let mut bytes = generate_dfa("a", "a").unwrap().to_bytes();
+    bytes.push(0);
+    assert!(matches!(
+        DfaRef::new(&bytes),
+        Err(DfaDecodingError::InvalidLength { .. })
+    ));
+}
@@ -0,0 +1,194 @@
+//! Converts a [`Dfa`] to an [`Expression`] by state elimination.
+
+use crate::dfa::Dfa;
+use crate::parser::Expression;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+mod tests;
+
+/// Returns the concatenation of two expressions, flattening nested concatenations and
+/// dropping empty strings.
+fn concatenate(left: Expression, right: Expression) -> Expression {
+    let mut sub_expressions = Vec::new();
+    for expression in [left, right] {
+        match expression {
+            Expression::EmptyString => {}
+            Expression::Concatenation(inner) => sub_expressions.extend(inner),
+            expression => sub_expressions.push(expression),
+        }
+    }
+    match sub_expressions.len() {
+        0 => Expression::EmptyString,
+        1 => sub_expressions.pop().unwrap(),
+        _ => Expression::Concatenation(sub_expressions),
+    }
+}
+
+/// Returns the choice between two expressions, flattening nested choices and dropping
+/// repeated arms, as well as empty strings which another arm already matches.
+fn choose(left: Expression, right: Expression) -> Expression {
+    let mut arms: Vec<Expression> = Vec::new();
+    for expression in [left, right] {
+        let expression_arms = match expression {
+            Expression::Choice(inner) => inner,
+            expression => vec![expression],
+        };
+        for arm in expression_arms {
+            if !arms.contains(&arm) {
+                arms.push(arm);
+            }
+        }
+    }
+    if arms
+        .iter()
+        .any(|arm| arm != &Expression::EmptyString && arm.is_nullable())
+    {
+        arms.retain(|arm| arm != &Expression::EmptyString);
+    }
+    match arms.len() {
+        1 => arms.pop().unwrap(),
+        _ => Expression::Choice(arms),
+    }
+}
+
+/// Returns the closure of an expression, removing redundant empty strings and closures
+/// within it.
+fn close(expression: Expression) -> Expression {
+    match expression {
+        Expression::EmptyString => Expression::EmptyString,
+        Expression::Closure(inner) => Expression::Closure(inner),
+        Expression::Choice(arms) => {
+            let arms: Vec<Expression> = arms
+                .into_iter()
+                .filter(|arm| arm != &Expression::EmptyString)
+                .collect();
+            match arms.len() {
+                0 => Expression::EmptyString,
+                1 => close(arms.into_iter().next().unwrap()),
+                _ => Expression::Closure(Box::from(Expression::Choice(arms))),
+            }
+        }
+        expression => Expression::Closure(Box::from(expression)),
+    }
+}
+
+/// A generalized NFA, whose transitions are labelled by expressions rather than chars.
+///
+/// There is at most one transition between each ordered pair of states.
+struct Gnfa {
+    transitions: BTreeMap<(usize, usize), Expression>,
+}
+
+impl Gnfa {
+    /// Adds a transition, combining it by choice with any existing transition between the
+    /// same states.
+    fn add_transition(&mut self, from: usize, to: usize, expression: Expression) {
+        let expression = match self.transitions.remove(&(from, to)) {
+            Some(existing) => choose(existing, expression),
+            None => expression,
+        };
+        self.transitions.insert((from, to), expression);
+    }
+
+    /// Returns the cost of eliminating a state: the number of transitions into it times the
+    /// number out of it, ignoring any loop. This is the number of transitions which its
+    /// elimination creates or extends.
+    fn elimination_cost(&self, state: usize) -> usize {
+        let n_in = self
+            .transitions
+            .keys()
+            .filter(|(from, to)| *to == state && *from != state)
+            .count();
+        let n_out = self
+            .transitions
+            .keys()
+            .filter(|(from, to)| *from == state && *to != state)
+            .count();
+        n_in * n_out
+    }
+
+    /// Removes a state, replacing each path through it with a transition bypassing it.
+    fn eliminate(&mut self, state: usize) {
+        let repeated = self.transitions.remove(&(state, state)).map(close);
+        let incoming: Vec<(usize, Expression)> = self
+            .transitions
+            .iter()
+            .filter(|((_, to), _)| *to == state)
+            .map(|((from, _), expression)| (*from, expression.clone()))
+            .collect();
+        let outgoing: Vec<(usize, Expression)> = self
+            .transitions
+            .iter()
+            .filter(|((from, _), _)| *from == state)
+            .map(|((_, to), expression)| (*to, expression.clone()))
+            .collect();
+        self.transitions
+            .retain(|(from, to), _| *from != state && *to != state);
+        for (from, incoming_expression) in &incoming {
+            for (to, outgoing_expression) in &outgoing {
+                let mut bypass = incoming_expression.clone();
+                if let Some(repeated) = &repeated {
+                    bypass = concatenate(bypass, repeated.clone());
+                }
+                bypass = concatenate(bypass, outgoing_expression.clone());
+                self.add_transition(*from, *to, bypass);
+            }
+        }
+    }
+}
+
+impl Dfa {
+    /// Returns an expression matching exactly the strings accepted by the DFA, which is
+    /// [`Expression::EmptySet`] if it accepts no strings.
+    ///
+    /// Uses state elimination. The DFA is extended with a new start state, with an empty
+    /// transition to the old one, and a new final state, with an empty transition from each
+    /// accepting state. Its states are then removed one at a time, replacing each path
+    /// through the removed state by a transition labelled with an equivalent expression, until
+    /// a single transition from the new start state to the new final state remains.
+    ///
+    /// States which cannot take part in accepting a string are removed first. Of the others,
+    /// the state whose removal creates or extends the fewest transitions is always removed
+    /// next, which tends to keep the expression short. The expression is simplified as it is
+    /// built, by flattening nested concatenations and choices, dropping redundant empty
+    /// strings, and removing repeated choice arms, then finally by [`Expression::simplify`].
+    pub fn to_expression(&self) -> Expression {
+        let useful_states = self.useful_states();
+        if useful_states.is_empty() {
+            return Expression::EmptySet;
+        }
+        let initial_state = self.n_states;
+        let final_state = self.n_states + 1;
+        let mut gnfa = Gnfa {
+            transitions: BTreeMap::new(),
+        };
+        gnfa.add_transition(initial_state, self.start_state, Expression::EmptyString);
+        let is_useful: BTreeSet<usize> = useful_states.iter().copied().collect();
+        for state in &useful_states {
+            if self.accepting_states.contains(state) {
+                gnfa.add_transition(*state, final_state, Expression::EmptyString);
+            }
+            for (c, target_state) in self.sorted_transitions(*state) {
+                if is_useful.contains(&target_state) {
+                    gnfa.add_transition(*state, target_state, Expression::Char(c));
+                }
+            }
+        }
+        let mut remaining_states = is_useful;
+        while let Some(state) = remaining_states
+            .iter()
+            .copied()
+            .min_by_key(|state| gnfa.elimination_cost(*state))
+        {
+            gnfa.eliminate(state);
+            remaining_states.remove(&state);
+        }
+        // Some state is useful, so a path from the start state to an accepting state remains.
+        gnfa.transitions
+            .remove(&(initial_state, final_state))
+            .unwrap()
+            .simplify()
+            .into_expression()
+    }
+}
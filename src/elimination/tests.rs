@@ -0,0 +1,76 @@
+use super::*;
+use crate::annotator::annotate_ast;
+use crate::dfa::generate_dfa as generate_dfa_from_ast;
+use crate::generate_dfa;
+use std::collections::HashSet;
+
+/// Constructs the DFA of an expression over the given alphabet, without lexing or parsing.
+fn expression_dfa(expression: Expression, alphabet: &str) -> Dfa {
+    generate_dfa_from_ast(
+        annotate_ast(expression).unwrap(),
+        alphabet.chars().collect(),
+    )
+}
+
+#[test]
+fn test_to_expression_is_equivalent() {
+    let cases = [
+        "(a|b)*abb",
+        "ab|ba|()",
+        "a(b|c)*a|c",
+        "(a|b)(a|b)(a|b)",
+        "(ab*a|b)*",
+        "a*b*c*",
+        "()",
+    ];
+    for raw_expression in cases {
+        let dfa = generate_dfa(raw_expression, "abc").unwrap();
+        let expression = dfa.to_expression();
+        let round_trip = expression_dfa(expression, "abc");
+        assert!(round_trip.is_equivalent(&dfa), "{}", raw_expression);
+    }
+}
+
+#[test]
+fn test_to_expression_of_simple_dfas() {
+    let cases = [
+        (
+            "ab",
+            Expression::Concatenation(vec![Expression::Char('a'), Expression::Char('b')]),
+        ),
+        ("a*", Expression::Closure(Box::from(Expression::Char('a')))),
+        (
+            "a|b",
+            Expression::Choice(vec![Expression::Char('a'), Expression::Char('b')]),
+        ),
+        ("()", Expression::EmptyString),
+    ];
+    for (raw_expression, expected_output) in cases {
+        let dfa = generate_dfa(raw_expression, "ab").unwrap().minimize();
+        assert_eq!(dfa.to_expression(), expected_output, "{}", raw_expression);
+    }
+}
+
+#[test]
+fn test_to_expression_of_empty_language() {
+    let dfa = generate_dfa("a", "ab").unwrap();
+    let empty = Dfa {
+        accepting_states: HashSet::new(),
+        ..dfa
+    };
+    assert_eq!(empty.to_expression(), Expression::EmptySet);
+}
+
+#[test]
+fn test_simplifying_constructors() {
+    let a = || Expression::Char('a');
+    let a_star = || Expression::Closure(Box::from(a()));
+    assert_eq!(concatenate(Expression::EmptyString, a()), a());
+    assert_eq!(choose(a(), a()), a());
+    assert_eq!(choose(Expression::EmptyString, a_star()), a_star());
+    assert_eq!(close(a_star()), a_star());
+    assert_eq!(
+        close(Expression::Choice(vec![Expression::EmptyString, a()])),
+        a_star()
+    );
+}
@@ -0,0 +1,39 @@
+//! Decides whether two [`Dfa`]s accept the same language.
+
+use crate::dfa::Dfa;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+#[cfg(test)]
+mod tests;
+
+impl Dfa {
+    /// Returns whether the two DFAs accept exactly the same strings.
+    ///
+    /// Explores the product of the two DFAs breadth-first from their start states, where
+    /// [`None`] stands for having left a DFA. The DFAs are equivalent if no reachable pair of
+    /// states disagrees on acceptance.
+    pub fn is_equivalent(&self, other: &Dfa) -> bool {
+        let symbols: BTreeSet<char> = self.symbols().union(&other.symbols()).copied().collect();
+        let start_pair = (Some(self.start_state), Some(other.start_state));
+        let mut visited = HashSet::from([start_pair]);
+        let mut queue = VecDeque::from([start_pair]);
+        while let Some((state, other_state)) = queue.pop_front() {
+            let is_accepting = state.is_some_and(|s| self.accepting_states.contains(&s));
+            let other_is_accepting =
+                other_state.is_some_and(|s| other.accepting_states.contains(&s));
+            if is_accepting != other_is_accepting {
+                return false;
+            }
+            for c in &symbols {
+                let next_pair = (
+                    state.and_then(|s| self.next_state(s, *c)),
+                    other_state.and_then(|s| other.next_state(s, *c)),
+                );
+                if next_pair != (None, None) && visited.insert(next_pair) {
+                    queue.push_back(next_pair);
+                }
+            }
+        }
+        true
+    }
+}
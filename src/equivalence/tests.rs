@@ -0,0 +1,36 @@
+use crate::generate_dfa;
+
+#[test]
+fn test_equivalent_expressions() {
+    let alphabet = "ab";
+    let pairs = [
+        ("(a|b)*", "(a*b*)*"),
+        ("a(ba)*", "(ab)*a"),
+        ("aa|ba", "(a|b)a"),
+        ("", "()*"),
+    ];
+    for (left, right) in pairs {
+        let left_dfa = generate_dfa(left, alphabet).unwrap();
+        let right_dfa = generate_dfa(right, alphabet).unwrap();
+        assert!(left_dfa.is_equivalent(&right_dfa), "{} != {}", left, right);
+        assert!(right_dfa.is_equivalent(&left_dfa), "{} != {}", right, left);
+    }
+}
+
+#[test]
+fn test_inequivalent_expressions() {
+    let alphabet = "ab";
+    let pairs = [("a*", "(a|b)*"), ("ab", "ba"), ("a*", "aa*"), ("a", "")];
+    for (left, right) in pairs {
+        let left_dfa = generate_dfa(left, alphabet).unwrap();
+        let right_dfa = generate_dfa(right, alphabet).unwrap();
+        assert!(!left_dfa.is_equivalent(&right_dfa), "{} == {}", left, right);
+        assert!(!right_dfa.is_equivalent(&left_dfa), "{} == {}", right, left);
+    }
+}
+
+#[test]
+fn test_minimized_dfa_is_equivalent() {
+    let dfa = generate_dfa("(a|b)*abb|ab*", "ab").unwrap();
+    assert!(dfa.is_equivalent(&dfa.minimize()));
+}
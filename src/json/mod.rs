@@ -0,0 +1,606 @@
+//! Saves a [`Dfa`] to, and loads it from, a human-readable JSON document.
+
+use crate::dfa::Dfa;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Write;
+
+#[cfg(test)]
+mod tests;
+
+/// The fields of a document, in the order they are written.
+const FIELDS: [&str; 5] = ["alphabet", "states", "start", "accepting", "transitions"];
+
+/// The deepest nesting of arrays and objects which is parsed, bounding the recursion of the
+/// parser. A valid document nests three deep.
+const MAX_DEPTH: usize = 32;
+
+/// Wraps all errors which can occur while loading a [`Dfa`] from JSON.
+///
+/// Paths are JSON pointers to the invalid value, e.g. `/transitions/0/a`, where the empty
+/// path is the whole document.
+#[derive(Debug, PartialEq)]
+pub enum DfaJsonError {
+    /// Represents that the document is not valid JSON.
+    Syntax {
+        /// The byte offset at which parsing failed.
+        offset: usize,
+    },
+    /// Represents that the document does not have one of the fields of the schema.
+    MissingField {
+        /// The path of the missing field.
+        path: String,
+    },
+    /// Represents that the document has a field which is not in the schema.
+    UnexpectedField {
+        /// The path of the field.
+        path: String,
+    },
+    /// Represents that a field, symbol or accepting state is given more than once.
+    DuplicateEntry {
+        /// The path of the repeated entry.
+        path: String,
+    },
+    /// Represents that a value is of the wrong type.
+    InvalidType {
+        /// The path of the value.
+        path: String,
+        /// A description of the type the value should have.
+        expected: &'static str,
+    },
+    /// Represents that a symbol is not a string of exactly one char, or is a transition on a
+    /// char which is not in the alphabet.
+    InvalidSymbol {
+        /// The path of the symbol.
+        path: String,
+    },
+    /// Represents that a state is not less than the number of states.
+    StateOutOfRange {
+        /// The path of the state.
+        path: String,
+    },
+    /// Represents that the transitions do not have an entry for every state.
+    InvalidLength {
+        /// The number of states.
+        expected: usize,
+        /// The number of entries found.
+        found: usize,
+    },
+}
+
+/// Describes the value at path for an error message.
+fn describe_path(path: &str) -> String {
+    match path {
+        "" => String::from("the document"),
+        _ => format!("{:?}", path),
+    }
+}
+
+impl Display for DfaJsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            DfaJsonError::Syntax { offset } => {
+                write!(f, "The JSON is invalid at byte offset {}.", offset)
+            }
+            DfaJsonError::MissingField { path } => {
+                write!(f, "The field {} is missing.", describe_path(path))
+            }
+            DfaJsonError::UnexpectedField { path } => {
+                write!(
+                    f,
+                    "The field {} is not part of the schema.",
+                    describe_path(path)
+                )
+            }
+            DfaJsonError::DuplicateEntry { path } => {
+                write!(f, "The entry {} is a duplicate.", describe_path(path))
+            }
+            DfaJsonError::InvalidType { path, expected } => write!(
+                f,
+                "The value of {} should be {}.",
+                describe_path(path),
+                expected
+            ),
+            DfaJsonError::InvalidSymbol { path } => write!(
+                f,
+                "The symbol {} is not a single char of the alphabet.",
+                describe_path(path)
+            ),
+            DfaJsonError::StateOutOfRange { path } => {
+                write!(f, "The state {} is out of range.", describe_path(path))
+            }
+            DfaJsonError::InvalidLength { expected, found } => write!(
+                f,
+                "There are {} states, but transitions has {} entries.",
+                expected, found
+            ),
+        }
+    }
+}
+
+/// Represents a parsed JSON value. Numbers are kept as written, since only non-negative
+/// integers are valid in a document.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    /// The fields of an object in the order written, keeping any duplicate keys.
+    Object(Vec<(String, Value)>),
+}
+
+/// Parses JSON text, tracking the byte offset reached.
+struct Parser<'a> {
+    json: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn syntax_error(&self) -> DfaJsonError {
+        DfaJsonError::Syntax {
+            offset: self.offset,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    /// Consumes the byte b, failing if it is not next.
+    fn expect(&mut self, b: u8) -> Result<(), DfaJsonError> {
+        match self.peek() == Some(b) {
+            true => {
+                self.offset += 1;
+                Ok(())
+            }
+            false => Err(self.syntax_error()),
+        }
+    }
+
+    /// Parses a document consisting of a single value, surrounded by optional whitespace.
+    fn parse_document(&mut self) -> Result<Value, DfaJsonError> {
+        let value = self.parse_value(0)?;
+        self.skip_whitespace();
+        match self.offset == self.json.len() {
+            true => Ok(value),
+            false => Err(self.syntax_error()),
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<Value, DfaJsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') | Some(b'[') if depth == MAX_DEPTH => Err(self.syntax_error()),
+            Some(b'{') => self.parse_object(depth + 1),
+            Some(b'[') => self.parse_array(depth + 1),
+            Some(b'"') => self.parse_string().map(Value::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => {
+                for (literal, value) in [
+                    ("true", Value::Bool(true)),
+                    ("false", Value::Bool(false)),
+                    ("null", Value::Null),
+                ] {
+                    if self.json[self.offset..].starts_with(literal) {
+                        self.offset += literal.len();
+                        return Ok(value);
+                    }
+                }
+                Err(self.syntax_error())
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<Value, DfaJsonError> {
+        self.expect(b'{')?;
+        let mut fields = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.offset += 1;
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            fields.push((key, self.parse_value(depth)?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b'}') => {
+                    self.offset += 1;
+                    return Ok(Value::Object(fields));
+                }
+                _ => return Err(self.syntax_error()),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<Value, DfaJsonError> {
+        self.expect(b'[')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.parse_value(depth)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    return Ok(Value::Array(values));
+                }
+                _ => return Err(self.syntax_error()),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, DfaJsonError> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            match self.json[self.offset..].chars().next() {
+                Some('"') => {
+                    self.offset += 1;
+                    return Ok(string);
+                }
+                Some('\\') => {
+                    self.offset += 1;
+                    string.push(self.parse_escape()?);
+                }
+                Some(c) if c >= ' ' => {
+                    self.offset += c.len_utf8();
+                    string.push(c);
+                }
+                _ => return Err(self.syntax_error()),
+            }
+        }
+    }
+
+    /// Parses the escape following a backslash in a string.
+    fn parse_escape(&mut self) -> Result<char, DfaJsonError> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.offset += 1;
+                let start = self.offset;
+                let mut code = self.parse_hex()?;
+                // A char outside the basic multilingual plane is escaped as a surrogate pair.
+                if (0xD800..0xDC00).contains(&code) {
+                    self.expect(b'\\')?;
+                    self.expect(b'u')?;
+                    let low = self.parse_hex()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(DfaJsonError::Syntax { offset: start });
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+                return char::from_u32(code).ok_or(DfaJsonError::Syntax { offset: start });
+            }
+            _ => return Err(self.syntax_error()),
+        };
+        self.offset += 1;
+        Ok(c)
+    }
+
+    /// Parses the four hex digits of a `\u` escape.
+    fn parse_hex(&mut self) -> Result<u32, DfaJsonError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .peek()
+                .and_then(|b| char::from(b).to_digit(16))
+                .ok_or_else(|| self.syntax_error())?;
+            code = 16 * code + digit;
+            self.offset += 1;
+        }
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<Value, DfaJsonError> {
+        let start = self.offset;
+        if self.peek() == Some(b'-') {
+            self.offset += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.offset += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.syntax_error()),
+        }
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            self.expect_digits()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.offset += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.offset += 1;
+            }
+            self.expect_digits()?;
+        }
+        Ok(Value::Number(String::from(&self.json[start..self.offset])))
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    /// Consumes one or more digits.
+    fn expect_digits(&mut self) -> Result<(), DfaJsonError> {
+        match self.peek() {
+            Some(b'0'..=b'9') => {
+                self.skip_digits();
+                Ok(())
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+}
+
+/// Returns the JSON pointer to the entry key of the value at path.
+fn pointer(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Returns the values of the fields of the object at path, in the order of names, checking
+/// that it has exactly those fields.
+fn fields<'v, const N: usize>(
+    value: &'v Value,
+    path: &str,
+    names: [&str; N],
+) -> Result<[&'v Value; N], DfaJsonError> {
+    let Value::Object(entries) = value else {
+        return Err(DfaJsonError::InvalidType {
+            path: String::from(path),
+            expected: "an object",
+        });
+    };
+    let mut found = [None; N];
+    for (key, value) in entries {
+        let path = pointer(path, key);
+        let Some(i) = names.iter().position(|name| name == key) else {
+            return Err(DfaJsonError::UnexpectedField { path });
+        };
+        if found[i].replace(value).is_some() {
+            return Err(DfaJsonError::DuplicateEntry { path });
+        }
+    }
+    for (name, value) in names.iter().zip(found) {
+        if value.is_none() {
+            return Err(DfaJsonError::MissingField {
+                path: pointer(path, name),
+            });
+        }
+    }
+    Ok(found.map(Option::unwrap))
+}
+
+fn as_array<'v>(value: &'v Value, path: &str) -> Result<&'v [Value], DfaJsonError> {
+    match value {
+        Value::Array(values) => Ok(values),
+        _ => Err(DfaJsonError::InvalidType {
+            path: String::from(path),
+            expected: "an array",
+        }),
+    }
+}
+
+/// Returns the value of a non-negative integer, saturating at [`usize::MAX`].
+fn as_integer(value: &Value, path: &str) -> Result<usize, DfaJsonError> {
+    match value {
+        Value::Number(number) if number.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok(number.bytes().fold(0usize, |n, b| {
+                n.saturating_mul(10).saturating_add(usize::from(b - b'0'))
+            }))
+        }
+        _ => Err(DfaJsonError::InvalidType {
+            path: String::from(path),
+            expected: "a non-negative integer",
+        }),
+    }
+}
+
+fn as_state(value: &Value, path: &str, n_states: usize) -> Result<usize, DfaJsonError> {
+    match as_integer(value, path)? {
+        state if state < n_states => Ok(state),
+        _ => Err(DfaJsonError::StateOutOfRange {
+            path: String::from(path),
+        }),
+    }
+}
+
+/// Returns the only char of symbol, which is the string at path.
+fn as_symbol(symbol: &str, path: &str) -> Result<char, DfaJsonError> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(DfaJsonError::InvalidSymbol {
+            path: String::from(path),
+        }),
+    }
+}
+
+/// Quotes s as a JSON string, escaping only what must be escaped.
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Dfa {
+    /// Encodes the DFA as a human-readable JSON document, which [`Dfa::from_json`] loads.
+    ///
+    /// The document is an object with the fields:
+    ///
+    /// | Field         | Value                                                                 |
+    /// |---------------|-----------------------------------------------------------------------|
+    /// | `alphabet`    | The chars of the alphabet in ascending order, each a one-char string. |
+    /// | `states`      | The number of states, N. The states of the DFA are thus 0..N.         |
+    /// | `start`       | The start state.                                                      |
+    /// | `accepting`   | The accepting states in ascending order.                              |
+    /// | `transitions` | An array with an object per state, mapping each char with a transition out of the state to the state it reaches. |
+    ///
+    /// For example, the DFA of "a" over "ab" is:
+    ///
+    /// ```json
+    /// {
+    ///   "alphabet": ["a", "b"],
+    ///   "states": 2,
+    ///   "start": 0,
+    ///   "accepting": [1],
+    ///   "transitions": [
+    ///     {"a": 1},
+    ///     {}
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// The output only depends on the DFA, not on the order of its hash maps, so equal DFAs
+    /// always give equal documents.
+    pub fn to_json(&self) -> String {
+        let alphabet: Vec<String> = self
+            .alphabet
+            .iter()
+            .map(|c| quote(&c.to_string()))
+            .collect();
+        let mut accepting_states: Vec<&usize> = self.accepting_states.iter().collect();
+        accepting_states.sort_unstable();
+        let accepting_states: Vec<String> =
+            accepting_states.iter().map(|s| s.to_string()).collect();
+        let transitions: Vec<String> = (0..self.n_states)
+            .map(|state| {
+                let transitions: Vec<String> = self
+                    .sorted_transitions(state)
+                    .into_iter()
+                    .map(|(c, target_state)| format!("{}: {}", quote(&c.to_string()), target_state))
+                    .collect();
+                format!("    {{{}}}", transitions.join(", "))
+            })
+            .collect();
+        let mut json = String::from("{\n");
+        writeln!(json, "  \"alphabet\": [{}],", alphabet.join(", ")).unwrap();
+        writeln!(json, "  \"states\": {},", self.n_states).unwrap();
+        writeln!(json, "  \"start\": {},", self.start_state).unwrap();
+        writeln!(json, "  \"accepting\": [{}],", accepting_states.join(", ")).unwrap();
+        match transitions.is_empty() {
+            true => json.push_str("  \"transitions\": []\n"),
+            false => write!(
+                json,
+                "  \"transitions\": [\n{}\n  ]\n",
+                transitions.join(",\n")
+            )
+            .unwrap(),
+        }
+        json.push('}');
+        json
+    }
+
+    /// Decodes a DFA from a JSON document with the schema described in [`Dfa::to_json`].
+    ///
+    /// Any JSON formatting is accepted, and the fields may be in any order, but the document
+    /// is otherwise validated strictly: every field must be present exactly once and no other
+    /// fields may be, every state must be less than the number of states, and every
+    /// transition must be on a char of the alphabet.
+    pub fn from_json(json: &str) -> Result<Dfa, DfaJsonError> {
+        let document = Parser { json, offset: 0 }.parse_document()?;
+        let [alphabet, states, start, accepting, transitions] = fields(&document, "", FIELDS)?;
+        let alphabet = as_array(alphabet, "/alphabet")?
+            .iter()
+            .enumerate()
+            .try_fold(BTreeSet::new(), |mut alphabet, (i, symbol)| {
+                let path = format!("/alphabet/{}", i);
+                let Value::String(symbol) = symbol else {
+                    return Err(DfaJsonError::InvalidType {
+                        path,
+                        expected: "a string",
+                    });
+                };
+                match alphabet.insert(as_symbol(symbol, &path)?) {
+                    true => Ok(alphabet),
+                    false => Err(DfaJsonError::DuplicateEntry { path }),
+                }
+            })?;
+        let n_states = as_integer(states, "/states")?;
+        let transitions = as_array(transitions, "/transitions")?;
+        if transitions.len() != n_states {
+            return Err(DfaJsonError::InvalidLength {
+                expected: n_states,
+                found: transitions.len(),
+            });
+        }
+        let start_state = as_state(start, "/start", n_states)?;
+        let mut accepting_states = HashSet::new();
+        for (i, state) in as_array(accepting, "/accepting")?.iter().enumerate() {
+            let path = format!("/accepting/{}", i);
+            if !accepting_states.insert(as_state(state, &path, n_states)?) {
+                return Err(DfaJsonError::DuplicateEntry { path });
+            }
+        }
+        let mut transition_function: HashMap<usize, HashMap<char, usize>> = HashMap::new();
+        for (state, state_transitions) in transitions.iter().enumerate() {
+            let path = format!("/transitions/{}", state);
+            let Value::Object(state_transitions) = state_transitions else {
+                return Err(DfaJsonError::InvalidType {
+                    path,
+                    expected: "an object",
+                });
+            };
+            for (symbol, target_state) in state_transitions {
+                let path = pointer(&path, symbol);
+                let c = as_symbol(symbol, &path)?;
+                if !alphabet.contains(&c) {
+                    return Err(DfaJsonError::InvalidSymbol { path });
+                }
+                let target_state = as_state(target_state, &path, n_states)?;
+                if transition_function
+                    .entry(state)
+                    .or_default()
+                    .insert(c, target_state)
+                    .is_some()
+                {
+                    return Err(DfaJsonError::DuplicateEntry { path });
+                }
+            }
+        }
+        Ok(Dfa {
+            n_states,
+            start_state,
+            accepting_states,
+            transition_function,
+            alphabet,
+        })
+    }
+}
@@ -0,0 +1,227 @@
+use super::*;
+use crate::generate_dfa;
+
+/// The document of the DFA of "a" over "ab".
+const JSON_A: &str = r#"{
+  "alphabet": ["a", "b"],
+  "states": 2,
+  "start": 0,
+  "accepting": [1],
+  "transitions": [
+    {"a": 1},
+    {}
+  ]
+}"#;
+
+/// Returns the error from loading json.
+fn error(json: &str) -> DfaJsonError {
+    Dfa::from_json(json).unwrap_err()
+}
+
+/// Returns the document of the DFA of "a" over "ab", with the given field replaced.
+fn replace_field(name: &str, value: &str) -> String {
+    JSON_A
+        .lines()
+        .map(|line| match line.starts_with(&format!("  \"{}\":", name)) {
+            true => format!("  \"{}\": {},", name, value),
+            false => String::from(line),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_to_json() {
+    let dfa = generate_dfa("a", "ab").unwrap();
+    assert_eq!(dfa.to_json(), JSON_A);
+}
+
+#[test]
+fn test_round_trip() {
+    let cases = [
+        ("(a|b)*abb", "ab"),
+        ("", "ab"),
+        ("∅", "a"),
+        ("a⟹(🦀)*", "a⟹🦀"),
+        ("^(\\ba|b\\B| )*$", "ab "),
+        ("\"(\n|\t|\u{1})*/~", "\"\n\t\u{1}/~"),
+    ];
+    for (input_expression, alphabet) in cases {
+        let dfa = generate_dfa(input_expression, alphabet).unwrap();
+        let json = dfa.to_json();
+        assert_eq!(Dfa::from_json(&json).unwrap(), dfa, "{}", json);
+    }
+}
+
+#[test]
+fn test_from_json_formatting() {
+    let json = r#" {"transitions":[{"\u0061":1},{}],"accepting":[1],"start":0,
+        "states":2,"alphabet":["a","\ud83e\udd80"]} "#;
+    let dfa = Dfa::from_json(json).unwrap();
+    assert_eq!(dfa, generate_dfa("a", "a🦀").unwrap());
+}
+
+#[test]
+fn test_invalid_syntax() {
+    for (json, offset) in [
+        ("", 0),
+        ("{", 1),
+        ("{}}", 2),
+        ("[1,]", 3),
+        ("{\"a\" 1}", 5),
+        ("\"\\x\"", 2),
+        ("\"\\ud800\"", 7),
+        ("\"\\udc00\"", 3),
+        ("\"\n\"", 1),
+        ("01", 1),
+        ("1.", 2),
+        ("tru", 0),
+    ] {
+        assert_eq!(error(json), DfaJsonError::Syntax { offset }, "{}", json);
+    }
+    let nested = format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1));
+    assert_eq!(error(&nested), DfaJsonError::Syntax { offset: MAX_DEPTH });
+}
+
+#[test]
+fn test_invalid_fields() {
+    let path = |path: &str| String::from(path);
+    assert_eq!(
+        error("[]"),
+        DfaJsonError::InvalidType {
+            path: path(""),
+            expected: "an object"
+        }
+    );
+    assert_eq!(
+        error(&JSON_A.replace("\"start\"", "\"s/t~\"")),
+        DfaJsonError::UnexpectedField {
+            path: path("/s~1t~0")
+        }
+    );
+    assert_eq!(
+        error(&JSON_A.replace("\"start\": 0,", "")),
+        DfaJsonError::MissingField {
+            path: path("/start")
+        }
+    );
+    assert_eq!(
+        error(&JSON_A.replace("\"states\"", "\"start\"")),
+        DfaJsonError::DuplicateEntry {
+            path: path("/start")
+        }
+    );
+}
+
+#[test]
+fn test_invalid_values() {
+    let path = |path: &str| String::from(path);
+    let cases = [
+        (
+            replace_field("alphabet", "[\"a\", \"a\"]"),
+            DfaJsonError::DuplicateEntry {
+                path: path("/alphabet/1"),
+            },
+        ),
+        (
+            replace_field("alphabet", "[\"a\", \"bc\"]"),
+            DfaJsonError::InvalidSymbol {
+                path: path("/alphabet/1"),
+            },
+        ),
+        (
+            replace_field("alphabet", "[1]"),
+            DfaJsonError::InvalidType {
+                path: path("/alphabet/0"),
+                expected: "a string",
+            },
+        ),
+        (
+            replace_field("states", "3"),
+            DfaJsonError::InvalidLength {
+                expected: 3,
+                found: 2,
+            },
+        ),
+        (
+            replace_field("states", "99999999999999999999999"),
+            DfaJsonError::InvalidLength {
+                expected: usize::MAX,
+                found: 2,
+            },
+        ),
+        (
+            replace_field("start", "-1"),
+            DfaJsonError::InvalidType {
+                path: path("/start"),
+                expected: "a non-negative integer",
+            },
+        ),
+        (
+            replace_field("start", "1.0"),
+            DfaJsonError::InvalidType {
+                path: path("/start"),
+                expected: "a non-negative integer",
+            },
+        ),
+        (
+            replace_field("start", "2"),
+            DfaJsonError::StateOutOfRange {
+                path: path("/start"),
+            },
+        ),
+        (
+            replace_field("accepting", "{}"),
+            DfaJsonError::InvalidType {
+                path: path("/accepting"),
+                expected: "an array",
+            },
+        ),
+        (
+            replace_field("accepting", "[1, 1]"),
+            DfaJsonError::DuplicateEntry {
+                path: path("/accepting/1"),
+            },
+        ),
+        (
+            JSON_A.replace("{}", "[]"),
+            DfaJsonError::InvalidType {
+                path: path("/transitions/1"),
+                expected: "an object",
+            },
+        ),
+        (
+            JSON_A.replace("{}", "{\"c\": 0}"),
+            DfaJsonError::InvalidSymbol {
+                path: path("/transitions/1/c"),
+            },
+        ),
+        (
+            JSON_A.replace("{}", "{\"b\": 2}"),
+            DfaJsonError::StateOutOfRange {
+                path: path("/transitions/1/b"),
+            },
+        ),
+        (
+            JSON_A.replace("{}", "{\"b\": 0, \"b\": 1}"),
+            DfaJsonError::DuplicateEntry {
+                path: path("/transitions/1/b"),
+            },
+        ),
+    ];
+    for (json, expected) in cases {
+        assert_eq!(error(&json), expected, "{}", json);
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert_eq!(
+        error("[]").to_string(),
+        "The value of the document should be an object."
+    );
+    assert_eq!(
+        error(&JSON_A.replace("{}", "{\"b\": 2}")).to_string(),
+        "The state \"/transitions/1/b\" is out of range."
+    );
+}
@@ -0,0 +1,268 @@
+//! Answers questions about the language accepted by a [`Dfa`].
+
+use crate::big_count::BigCount;
+use crate::dfa::Dfa;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[cfg(test)]
+mod tests;
+
+/// Rebuilds the string which first reached state in a breadth-first search, by following the
+/// recorded predecessors back to the start state.
+fn rebuild_string(predecessors: &HashMap<usize, (usize, char)>, mut state: usize) -> String {
+    let mut chars = Vec::new();
+    while let Some((predecessor, c)) = predecessors.get(&state) {
+        chars.push(*c);
+        state = *predecessor;
+    }
+    chars.iter().rev().collect()
+}
+
+impl Dfa {
+    /// Returns whether the DFA accepts no strings at all.
+    pub fn is_empty(&self) -> bool {
+        self.shortest_accepted().is_none()
+    }
+
+    /// Returns whether the DFA accepts every string over its alphabet.
+    pub fn is_universal(&self) -> bool {
+        self.shortest_rejected().is_none()
+    }
+
+    /// Returns the shortest string accepted by the DFA, or [`None`] if it accepts no strings.
+    ///
+    /// Of the shortest accepted strings, the first in char order is returned.
+    pub fn shortest_accepted(&self) -> Option<String> {
+        let mut predecessors = HashMap::new();
+        let mut queue = VecDeque::from([self.start_state]);
+        let mut visited = vec![false; self.n_states];
+        visited[self.start_state] = true;
+        while let Some(state) = queue.pop_front() {
+            if self.accepting_states.contains(&state) {
+                return Some(rebuild_string(&predecessors, state));
+            }
+            for (c, target_state) in self.sorted_transitions(state) {
+                if !visited[target_state] {
+                    visited[target_state] = true;
+                    predecessors.insert(target_state, (state, c));
+                    queue.push_back(target_state);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the shortest string over the alphabet of the DFA which it rejects, or [`None`]
+    /// if it accepts every string.
+    ///
+    /// Of the shortest rejected strings, the first in char order is returned.
+    pub fn shortest_rejected(&self) -> Option<String> {
+        if !self.accepting_states.contains(&self.start_state) {
+            return Some(String::new());
+        }
+        let mut predecessors = HashMap::new();
+        let mut queue = VecDeque::from([self.start_state]);
+        let mut visited = vec![false; self.n_states];
+        visited[self.start_state] = true;
+        while let Some(state) = queue.pop_front() {
+            for c in &self.alphabet {
+                let target_state = self.next_state(state, *c);
+                let is_rejected =
+                    target_state.is_none_or(|target| !self.accepting_states.contains(&target));
+                if is_rejected {
+                    let mut rejected = rebuild_string(&predecessors, state);
+                    rejected.push(*c);
+                    return Some(rejected);
+                }
+                let target_state = target_state.unwrap();
+                if !visited[target_state] {
+                    visited[target_state] = true;
+                    predecessors.insert(target_state, (state, *c));
+                    queue.push_back(target_state);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the useful states in topological order of the transitions between them, or
+    /// [`None`] if those transitions contain a cycle.
+    ///
+    /// The useful states are those reachable from the start state, from which an accepting
+    /// state can be reached. Any string following a cycle among them can be pumped, so the
+    /// language is finite exactly when there is no such cycle.
+    fn useful_topological_order(&self) -> Option<Vec<usize>> {
+        let useful_states = self.useful_states();
+        let is_useful: HashSet<usize> = useful_states.iter().copied().collect();
+        let useful_targets = |state: usize| {
+            self.sorted_transitions(state)
+                .into_iter()
+                .map(|(_, target_state)| target_state)
+                .filter(|target_state| is_useful.contains(target_state))
+        };
+        let mut in_degrees: HashMap<usize, usize> = HashMap::new();
+        for state in &useful_states {
+            for target_state in useful_targets(*state) {
+                *in_degrees.entry(target_state).or_default() += 1;
+            }
+        }
+        let mut queue: VecDeque<usize> = useful_states
+            .iter()
+            .copied()
+            .filter(|state| !in_degrees.contains_key(state))
+            .collect();
+        let mut order = Vec::new();
+        while let Some(state) = queue.pop_front() {
+            order.push(state);
+            for target_state in useful_targets(state) {
+                let in_degree = in_degrees.get_mut(&target_state).unwrap();
+                *in_degree -= 1;
+                if *in_degree == 0 {
+                    queue.push_back(target_state);
+                }
+            }
+        }
+        match order.len() == useful_states.len() {
+            true => Some(order),
+            false => None,
+        }
+    }
+
+    /// Returns whether the DFA accepts only finitely many strings.
+    pub fn is_finite(&self) -> bool {
+        self.useful_topological_order().is_some()
+    }
+
+    /// Returns the number of strings accepted by the DFA, or [`None`] if it accepts infinitely
+    /// many.
+    pub fn language_size(&self) -> Option<BigCount> {
+        let order = self.useful_topological_order()?;
+        let mut counts: HashMap<usize, BigCount> = HashMap::new();
+        for state in order.iter().rev() {
+            let mut count = BigCount::from(u64::from(self.accepting_states.contains(state)));
+            for (_, target_state) in self.sorted_transitions(*state) {
+                if let Some(target_count) = counts.get(&target_state) {
+                    count += target_count;
+                }
+            }
+            counts.insert(*state, count);
+        }
+        Some(counts.remove(&self.start_state).unwrap_or_default())
+    }
+
+    /// Returns an iterator over the strings accepted by the DFA with at most max_length chars.
+    ///
+    /// Strings are yielded lazily in shortlex order: by length, then in char order among
+    /// strings of the same length.
+    pub fn words(&self, max_length: usize) -> Words<'_> {
+        let max_length = match self.is_finite() {
+            // No accepted string can visit a useful state twice.
+            true => max_length.min(self.n_states),
+            false => max_length,
+        };
+        Words {
+            dfa: self,
+            max_length,
+            length: 0,
+            finishes_in: Vec::new(),
+            stack: Vec::new(),
+            prefix: String::new(),
+            prefix_length: 0,
+        }
+    }
+}
+
+/// An iterator over the strings accepted by a [`Dfa`] in shortlex order, up to a length bound.
+///
+/// Created by [`Dfa::words`]. Strings of each length are found by a depth-first search in char
+/// order, which only enters states from which an accepting state can be reached using exactly
+/// the chars remaining.
+#[derive(Debug)]
+pub struct Words<'a> {
+    dfa: &'a Dfa,
+    /// The greatest length of string to yield.
+    max_length: usize,
+    /// The length of the strings currently being searched for.
+    length: usize,
+    /// `finishes_in[k][s]` represents whether an accepting state is reached from state s by some
+    /// string of exactly k chars. Extended as longer strings are searched for.
+    finishes_in: Vec<Vec<bool>>,
+    /// The transitions out of each state on the current search path, with the index of the
+    /// next transition to try. Empty if no search is in progress.
+    stack: Vec<(Vec<(char, usize)>, usize)>,
+    /// The chars on the current search path.
+    prefix: String,
+    /// The number of chars in prefix.
+    prefix_length: usize,
+}
+
+impl Words<'_> {
+    /// Extends finishes_in to cover strings of the current length.
+    fn extend_finishes_in(&mut self) {
+        while self.finishes_in.len() <= self.length {
+            let finishes_in = match self.finishes_in.last() {
+                None => (0..self.dfa.n_states)
+                    .map(|state| self.dfa.accepting_states.contains(&state))
+                    .collect(),
+                Some(previous) => (0..self.dfa.n_states)
+                    .map(|state| {
+                        self.dfa
+                            .sorted_transitions(state)
+                            .iter()
+                            .any(|(_, target_state)| previous[*target_state])
+                    })
+                    .collect(),
+            };
+            self.finishes_in.push(finishes_in);
+        }
+    }
+}
+
+impl Iterator for Words<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let Some((transitions, next_transition)) = self.stack.last_mut() else {
+                // Start the search for strings of the next length.
+                if self.length > self.max_length {
+                    return None;
+                }
+                self.extend_finishes_in();
+                let start_state = self.dfa.start_state;
+                self.length += 1;
+                if !self.finishes_in[self.length - 1][start_state] {
+                    continue;
+                }
+                if self.length == 1 {
+                    return Some(String::new());
+                }
+                self.stack
+                    .push((self.dfa.sorted_transitions(start_state), 0));
+                continue;
+            };
+            let Some((c, target_state)) = transitions.get(*next_transition).copied() else {
+                self.stack.pop();
+                if self.prefix.pop().is_some() {
+                    self.prefix_length -= 1;
+                }
+                continue;
+            };
+            *next_transition += 1;
+            // The length being searched for is one less than self.length.
+            let remaining = self.length - 1 - self.prefix_length - 1;
+            if !self.finishes_in[remaining][target_state] {
+                continue;
+            }
+            if remaining == 0 {
+                let mut word = self.prefix.clone();
+                word.push(c);
+                return Some(word);
+            }
+            self.prefix.push(c);
+            self.prefix_length += 1;
+            self.stack
+                .push((self.dfa.sorted_transitions(target_state), 0));
+        }
+    }
+}
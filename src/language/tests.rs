@@ -0,0 +1,163 @@
+use crate::dfa::Dfa;
+use crate::generate_dfa;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[test]
+fn test_shortest_accepted() {
+    let cases = [
+        ("(a|b)*abb", Some("abb")),
+        ("b*|a", Some("")),
+        ("bb|ab|ba", Some("ab")),
+        ("(b|a)b*a", Some("aa")),
+    ];
+    for (expression, expected_output) in cases {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert_eq!(
+            dfa.shortest_accepted().as_deref(),
+            expected_output,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_shortest_rejected() {
+    let cases = [
+        ("(a|b)*", None),
+        ("(a|b)*abb", Some("")),
+        ("()|a(a|b)*", Some("b")),
+        ("(a|b)*|aa", None),
+        ("()|(a|b)(a|b)*b|a|b", Some("aa")),
+    ];
+    for (expression, expected_output) in cases {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert_eq!(
+            dfa.shortest_rejected().as_deref(),
+            expected_output,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_universality_depends_on_alphabet() {
+    assert!(generate_dfa("a*", "a").unwrap().is_universal());
+    assert!(!generate_dfa("a*", "ab").unwrap().is_universal());
+    assert!(generate_dfa("", "").unwrap().is_universal());
+    assert!(!generate_dfa("(a|b)*abb", "ab").unwrap().is_universal());
+}
+
+#[test]
+fn test_is_empty() {
+    assert!(!generate_dfa("", "").unwrap().is_empty());
+    assert!(!generate_dfa("(a|b)*abb", "ab").unwrap().is_empty());
+    let dfa = Dfa {
+        n_states: 2,
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: BTreeSet::from(['a']),
+    };
+    assert!(dfa.is_empty());
+    assert!(!dfa.is_universal());
+    assert_eq!(dfa.shortest_rejected().as_deref(), Some(""));
+}
+
+#[test]
+fn test_is_finite() {
+    let cases = [
+        ("(a|b)*abb", false),
+        ("ab|ba|()", true),
+        ("a(b|())a", true),
+        ("ab*", false),
+    ];
+    for (expression, expected_output) in cases {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert_eq!(dfa.is_finite(), expected_output, "{}", expression);
+    }
+}
+
+#[test]
+fn test_is_finite_ignores_cycles_which_cannot_accept() {
+    // State 1 loops, but never reaches an accepting state.
+    let dfa = Dfa {
+        n_states: 3,
+        start_state: 0,
+        accepting_states: HashSet::from([2]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([('a', 1), ('b', 2)])),
+            (1, HashMap::from([('a', 1)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert!(dfa.is_finite());
+    assert_eq!(dfa.language_size().unwrap().to_u64(), Some(1));
+}
+
+#[test]
+fn test_language_size() {
+    let cases = [
+        ("(a|b)*abb", None),
+        ("ab|ba|()", Some(3)),
+        ("(a|b)(a|b)(a|b)", Some(8)),
+        ("(a|())(b|())", Some(4)),
+    ];
+    for (expression, expected_output) in cases {
+        let dfa = generate_dfa(expression, "ab").unwrap();
+        assert_eq!(
+            dfa.language_size().map(|size| size.to_u64().unwrap()),
+            expected_output,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_language_size_exceeds_u64() {
+    let expression = "(a|b)".repeat(70);
+    let dfa = generate_dfa(&expression, "ab").unwrap();
+    assert_eq!(
+        dfa.language_size().unwrap().to_string(),
+        "1180591620717411303424"
+    );
+}
+
+#[test]
+fn test_words_are_in_shortlex_order() {
+    let dfa = generate_dfa("(b|a)*a", "ab").unwrap();
+    let words: Vec<String> = dfa.words(3).collect();
+    assert_eq!(
+        words,
+        ["a", "aa", "ba", "aaa", "aba", "baa", "bba"].map(String::from)
+    );
+}
+
+#[test]
+fn test_words_of_finite_language() {
+    let dfa = generate_dfa("ab|ba|()|bab", "ab").unwrap();
+    let words: Vec<String> = dfa.words(usize::MAX).collect();
+    assert_eq!(words, ["", "ab", "ba", "bab"].map(String::from));
+    assert_eq!(dfa.words(2).count(), 3);
+}
+
+#[test]
+fn test_words_of_empty_language() {
+    let dfa = Dfa {
+        n_states: 1,
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transition_function: HashMap::from([(0, HashMap::from([('a', 0)]))]),
+        alphabet: BTreeSet::from(['a']),
+    };
+    assert_eq!(dfa.words(10).next(), None);
+}
+
+#[test]
+fn test_words_is_lazy() {
+    let dfa = generate_dfa("(a|b)*", "ab").unwrap();
+    let words: Vec<String> = dfa.words(usize::MAX).skip(3).take(4).collect();
+    assert_eq!(words, ["aa", "ab", "ba", "bb"].map(String::from));
+}
@@ -4,7 +4,9 @@
 //! - Every reserved token is representable by some char.
 //! - The input string does not contain any chars not mapped to [`Token`]s.
 
-use std::collections::HashMap;
+use crate::char_set::CharSet;
+use crate::unicode::property;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::fmt::Formatter;
 
@@ -36,10 +38,14 @@ pub struct ReservedTokenOverwriteError {
 
 impl Display for ReservedTokenOverwriteError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let token = match &self.overwritten_token {
+            Token::ReservedToken(token) => token.to_string(),
+            token => token.to_string(),
+        };
         write!(
             f,
-            "A user-defined character {} has overwritten a reserved token {}. Please check the documentation to see which characters are reserved.",
-            self.overwritten_string, self.overwritten_token
+            "The alphabet contains the character {}, which is reserved for the {} token. The characters |, *, (, ), ∅, ^, $ and \\ are reserved, so can not be in the alphabet; a class such as \\p{{S}} or \\p{{P}} can still match them over the alphabet of generate_range_dfa.",
+            self.overwritten_string, token
         )
     }
 }
@@ -66,12 +72,41 @@ impl Display for PrefixPropertyViolationError {
     }
 }
 
+/// Runtime error representing that an escape sequence in the input string is not a word
+/// boundary assertion or a known Unicode property class.
+#[derive(Debug, PartialEq)]
+pub struct InvalidEscapeError {
+    pub(crate) escape: String,
+}
+
+impl Display for InvalidEscapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "The escape sequence {} is not a word boundary assertion \\b or \\B, or a known Unicode property class, such as \\p{{L}} or \\p{{Greek}}.",
+            self.escape
+        )
+    }
+}
+
 /// Wraps all lexer-based errors.
 #[derive(Debug, PartialEq)]
 pub enum LexicalError {
     CharacterParsing(CharacterParsingError),
     ReservedTokenOverwrite(ReservedTokenOverwriteError),
     PrefixPropertyViolation(PrefixPropertyViolationError),
+    InvalidEscape(InvalidEscapeError),
+}
+
+impl Display for LexicalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            LexicalError::CharacterParsing(e) => write!(f, "{}", e),
+            LexicalError::ReservedTokenOverwrite(e) => write!(f, "{}", e),
+            LexicalError::PrefixPropertyViolation(e) => write!(f, "{}", e),
+            LexicalError::InvalidEscape(e) => write!(f, "{}", e),
+        }
+    }
 }
 
 /// Wraps a [`HashMap<String, Token>`], providing runtime guarantees.
@@ -121,6 +156,17 @@ impl TokenMap {
         Ok(())
     }
 
+    /// Returns the chars of the user-defined alphabet.
+    pub(crate) fn alphabet(&self) -> BTreeSet<char> {
+        self.token_map
+            .values()
+            .filter_map(|token| match token {
+                Token::Char(c) => Some(*c),
+                Token::ReservedToken(_) | Token::Property(_) => None,
+            })
+            .collect()
+    }
+
     /// Sanitising constructor.
     fn new(token_map: HashMap<String, Token>) -> Result<TokenMap, LexicalError> {
         TokenMap::verify_reserved_tokens_exist(&token_map)?;
@@ -135,6 +181,16 @@ pub(crate) enum ReservedToken {
     Closure,
     LeftPrecedence,
     RightPrecedence,
+    EmptySet,
+    StartAnchor,
+    EndAnchor,
+    /// Only produced by the escape sequence `\b`.
+    WordBoundary,
+    /// Only produced by the escape sequence `\B`.
+    NotWordBoundary,
+    /// Begins an escape sequence, such as a Unicode property class. Never produced by the
+    /// lexer, which replaces each escape sequence with the token it represents.
+    Escape,
 }
 
 impl Display for ReservedToken {
@@ -144,6 +200,12 @@ impl Display for ReservedToken {
             ReservedToken::Closure => write!(f, "Closure \"*\""),
             ReservedToken::LeftPrecedence => write!(f, "Left Precedence \"(\""),
             ReservedToken::RightPrecedence => write!(f, "Right Precedence \")\""),
+            ReservedToken::EmptySet => write!(f, "Empty Set \"∅\""),
+            ReservedToken::StartAnchor => write!(f, "Start Anchor \"^\""),
+            ReservedToken::EndAnchor => write!(f, "End Anchor \"$\""),
+            ReservedToken::WordBoundary => write!(f, "Word Boundary \"\\b\""),
+            ReservedToken::NotWordBoundary => write!(f, "Not Word Boundary \"\\B\""),
+            ReservedToken::Escape => write!(f, "Escape \"\\\""),
         }
     }
 }
@@ -157,6 +219,8 @@ impl Display for ReservedToken {
 pub(crate) enum Token {
     Char(char),
     ReservedToken(ReservedToken),
+    /// Matches any char of the alphabet with the Unicode property of the given canonical name.
+    Property(&'static str),
 }
 
 impl Display for Token {
@@ -164,6 +228,7 @@ impl Display for Token {
         match self {
             Token::Char(c) => write!(f, "Char \"{}\"", c),
             Token::ReservedToken(t) => write!(f, "Reserved Token {}", t),
+            Token::Property(name) => write!(f, "Property \"\\p{{{}}}\"", name),
         }
     }
 }
@@ -187,6 +252,22 @@ fn generate_reserved_token_map() -> HashMap<String, Token> {
             String::from(")"),
             Token::ReservedToken(ReservedToken::RightPrecedence),
         ),
+        (
+            String::from("∅"),
+            Token::ReservedToken(ReservedToken::EmptySet),
+        ),
+        (
+            String::from("^"),
+            Token::ReservedToken(ReservedToken::StartAnchor),
+        ),
+        (
+            String::from("$"),
+            Token::ReservedToken(ReservedToken::EndAnchor),
+        ),
+        (
+            String::from("\\"),
+            Token::ReservedToken(ReservedToken::Escape),
+        ),
     ])
 }
 
@@ -214,6 +295,37 @@ fn match_token<'a>(
     }))
 }
 
+/// Matches the escape sequence following an escape token at the start of input_string, which
+/// must be a word boundary assertion `b` or `B`, or a Unicode property class of the form
+/// `p{Name}`.
+fn match_escape(input_string: &str) -> Result<(Token, &str), LexicalError> {
+    if let Some(remaining) = input_string.strip_prefix('b') {
+        return Ok((Token::ReservedToken(ReservedToken::WordBoundary), remaining));
+    }
+    if let Some(remaining) = input_string.strip_prefix('B') {
+        return Ok((
+            Token::ReservedToken(ReservedToken::NotWordBoundary),
+            remaining,
+        ));
+    }
+    let name_and_remaining = input_string
+        .strip_prefix("p{")
+        .and_then(|rest| rest.split_once('}'));
+    if let Some((name, remaining)) = name_and_remaining {
+        if let Some((canonical_name, _)) = property(name) {
+            return Ok((Token::Property(canonical_name), remaining));
+        }
+    }
+    let escape: String = match name_and_remaining {
+        Some((name, _)) => format!("\\p{{{}}}", name),
+        None => match input_string.chars().next() {
+            Some(c) => format!("\\{}", c),
+            None => String::from("\\"),
+        },
+    };
+    Err(LexicalError::InvalidEscape(InvalidEscapeError { escape }))
+}
+
 /// Generates a [`Vec<Token>`] representing the input_string from the token_map.
 pub(crate) fn lex_string(
     token_map: &TokenMap,
@@ -222,8 +334,47 @@ pub(crate) fn lex_string(
     let mut token_stream: Vec<Token> = Vec::new();
     let mut remaining_input_string = input_string;
     while !remaining_input_string.is_empty() {
-        let token: Token;
+        let mut token: Token;
         (token, remaining_input_string) = match_token(token_map, remaining_input_string)?;
+        if token == Token::ReservedToken(ReservedToken::Escape) {
+            (token, remaining_input_string) = match_escape(remaining_input_string)?;
+        }
+        token_stream.push(token);
+    }
+    Ok(token_stream)
+}
+
+/// Generates a [`Vec<Token>`] representing the input_string over an alphabet of chars which
+/// may be too large to enumerate into a [`TokenMap`], such as all of Unicode.
+///
+/// Reserved tokens take precedence over the alphabet, so a reserved char in the alphabet can
+/// only be matched by a class, such as a Unicode property class.
+pub(crate) fn lex_string_over_set(
+    alphabet: &CharSet,
+    input_string: &str,
+) -> Result<Vec<Token>, LexicalError> {
+    let reserved_token_map = generate_reserved_token_map();
+    let mut token_stream: Vec<Token> = Vec::new();
+    let mut remaining_input_string = input_string;
+    while let Some(c) = remaining_input_string.chars().next() {
+        let reserved_token = reserved_token_map
+            .iter()
+            .find(|(string, _)| remaining_input_string.starts_with(string.as_str()));
+        let mut token: Token;
+        (token, remaining_input_string) = match reserved_token {
+            Some((string, token)) => (*token, &remaining_input_string[string.len()..]),
+            None if alphabet.contains(c) => {
+                (Token::Char(c), &remaining_input_string[c.len_utf8()..])
+            }
+            None => {
+                return Err(LexicalError::CharacterParsing(CharacterParsingError {
+                    unmatchable_char: c,
+                }))
+            }
+        };
+        if token == Token::ReservedToken(ReservedToken::Escape) {
+            (token, remaining_input_string) = match_escape(remaining_input_string)?;
+        }
         token_stream.push(token);
     }
     Ok(token_stream)
@@ -78,3 +78,117 @@ fn test_overwrite_reserved_token() {
         })
     );
 }
+
+// These chars were valid in alphabets before the tokens they are reserved for were added.
+#[test]
+fn test_overwrite_later_reserved_tokens() {
+    for (c, reserved_token) in [
+        ('∅', ReservedToken::EmptySet),
+        ('^', ReservedToken::StartAnchor),
+        ('$', ReservedToken::EndAnchor),
+        ('\\', ReservedToken::Escape),
+    ] {
+        let expression = format!("{}a", c);
+        let alphabet = format!("{}a", c);
+        let error = crate::generate_dfa(&expression, &alphabet).unwrap_err();
+        assert_eq!(
+            error,
+            crate::DfaGenerationError::Lexical(LexicalError::ReservedTokenOverwrite(
+                ReservedTokenOverwriteError {
+                    overwritten_string: c.to_string(),
+                    overwritten_token: Token::ReservedToken(reserved_token),
+                }
+            ))
+        );
+    }
+    let error = generate_token_map("$a").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The alphabet contains the character $, which is reserved for the End Anchor \"$\" token. The characters |, *, (, ), ∅, ^, $ and \\ are reserved, so can not be in the alphabet; a class such as \\p{S} or \\p{P} can still match them over the alphabet of generate_range_dfa."
+    );
+}
+
+#[test]
+fn test_token_match_empty_set() {
+    let token_map = generate_token_map("a").unwrap();
+    let expected_output = Ok(vec![
+        Token::ReservedToken(ReservedToken::EmptySet),
+        Token::ReservedToken(ReservedToken::Choice),
+        Token::Char('a'),
+    ]);
+    assert_eq!(lex_string(&token_map, "∅|a"), expected_output);
+    assert!(generate_token_map("a∅").is_err());
+}
+
+#[test]
+fn test_token_match_property() {
+    let token_map = generate_token_map("aα").unwrap();
+    let expected_output = Ok(vec![
+        Token::Property("Greek"),
+        Token::ReservedToken(ReservedToken::Closure),
+        Token::Char('a'),
+        Token::Property("Lu"),
+    ]);
+    assert_eq!(
+        lex_string(&token_map, "\\p{Grek}*a\\p{Uppercase_Letter}"),
+        expected_output
+    );
+}
+
+#[test]
+fn test_invalid_escape() {
+    let token_map = generate_token_map("a").unwrap();
+    let cases = [
+        ("\\p{Klingon}", "\\p{Klingon}"),
+        ("a\\d", "\\d"),
+        ("\\", "\\"),
+    ];
+    for (input_string, escape) in cases {
+        assert_eq!(
+            lex_string(&token_map, input_string),
+            Err(LexicalError::InvalidEscape(InvalidEscapeError {
+                escape: String::from(escape)
+            })),
+            "{}",
+            input_string
+        );
+    }
+    assert!(generate_token_map("a\\").is_err());
+}
+
+#[test]
+fn test_lex_over_set() {
+    let alphabet = CharSet::all();
+    let expected_output = Ok(vec![
+        Token::ReservedToken(ReservedToken::LeftPrecedence),
+        Token::Char('漢'),
+        Token::Property("P"),
+        Token::ReservedToken(ReservedToken::RightPrecedence),
+        Token::ReservedToken(ReservedToken::Closure),
+    ]);
+    assert_eq!(
+        lex_string_over_set(&alphabet, "(漢\\p{P})*"),
+        expected_output
+    );
+    let digits = CharSet::property("Nd").unwrap();
+    assert_eq!(
+        lex_string_over_set(&digits, "1a"),
+        Err(LexicalError::CharacterParsing(CharacterParsingError {
+            unmatchable_char: 'a'
+        }))
+    );
+}
+
+#[test]
+fn test_token_match_assertions() {
+    let token_map = generate_token_map("ab").unwrap();
+    let expected_output = Ok(vec![
+        Token::ReservedToken(ReservedToken::StartAnchor),
+        Token::ReservedToken(ReservedToken::WordBoundary),
+        Token::Char('a'),
+        Token::ReservedToken(ReservedToken::NotWordBoundary),
+        Token::Char('b'),
+        Token::ReservedToken(ReservedToken::EndAnchor),
+    ]);
+    assert_eq!(lex_string(&token_map, "^\\ba\\Bb$"), expected_output);
+}
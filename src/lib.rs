@@ -1,14 +1,66 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 mod annotator;
+mod big_count;
+mod binary;
+mod builder;
+mod byte_dfa;
+mod canonicalize;
+mod char_set;
+mod construction;
+mod counting;
+mod coverage;
 mod dfa;
+mod dfa_ref;
+mod elimination;
+mod equivalence;
+mod json;
+mod language;
 mod lexer;
+mod matcher;
+mod minimize;
+mod options;
 mod parser;
+mod range_dfa;
+mod render;
+// Public only so that the redfa-repl binary can run it; not part of the supported API, so
+// only built with the repl feature.
+#[cfg(feature = "repl")]
+#[doc(hidden)]
+pub mod repl;
+mod sample;
+mod simplify;
+mod trace;
+mod unicode;
+mod utf8;
 
 #[cfg(test)]
 mod tests;
 
+pub use big_count::BigCount;
+pub use binary::DfaDecodingError;
+pub use builder::Regex;
+pub use byte_dfa::ByteDfa;
+pub use char_set::CharSet;
+pub use construction::{Construction, Node, NodeKind, Position, PositionSymbol};
+pub use coverage::TestCase;
+pub use dfa::{Dfa, Neighbour};
+pub use dfa_ref::DfaRef;
+pub use json::DfaJsonError;
+pub use language::Words;
+pub use matcher::{Matcher, ReadError};
+pub use options::GenerationOptions;
+pub use parser::{Assertion, Expression};
+pub use range_dfa::RangeDfa;
+pub use sample::RandomSource;
+pub use simplify::{Simplification, SimplificationRule};
+pub use trace::{Trace, TraceEvent};
+pub use utf8::{InvalidEncodingError, Utf8Decoder};
+
 /// Represents an error during the creation of the DFA.
 #[derive(Debug, PartialEq)]
 pub enum DfaGenerationError {
@@ -21,6 +73,16 @@ pub enum DfaGenerationError {
     Annotation(annotator::AnnotationError),
 }
 
+impl Display for DfaGenerationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            DfaGenerationError::Lexical(e) => write!(f, "Lexical error: {}", e),
+            DfaGenerationError::Syntactic(e) => write!(f, "Syntax error: {}", e),
+            DfaGenerationError::Annotation(e) => write!(f, "Annotation error: {}", e),
+        }
+    }
+}
+
 impl From<lexer::LexicalError> for DfaGenerationError {
     fn from(value: lexer::LexicalError) -> Self {
         DfaGenerationError::Lexical(value)
@@ -41,9 +103,89 @@ impl From<annotator::AnnotationError> for DfaGenerationError {
 
 /// Generates a DFA from an input regular expression string and alphabet.
 pub fn generate_dfa(raw_expression: &str, alphabet: &str) -> Result<dfa::Dfa, DfaGenerationError> {
+    generate_dfa_with_options(raw_expression, alphabet, &GenerationOptions::default())
+}
+
+/// Generates a DFA from an input regular expression string and alphabet, as controlled by the
+/// given options.
+pub fn generate_dfa_with_options(
+    raw_expression: &str,
+    alphabet: &str,
+    options: &GenerationOptions,
+) -> Result<dfa::Dfa, DfaGenerationError> {
+    let sanitised_alphabet = lexer::generate_token_map(alphabet)?;
+    let lexed_expression = lexer::lex_string(&sanitised_alphabet, raw_expression)?;
+    let parsed_expression = parser::parse(lexed_expression)?;
+    let alphabet = sanitised_alphabet.alphabet();
+    let annotated_expression = annotator::annotate_ast(
+        options.apply(parsed_expression, &alphabet.iter().copied().collect()),
+    )?;
+    Ok(dfa::generate_dfa(annotated_expression, alphabet))
+}
+
+/// Generates a DFA with range-based transitions from an input regular expression string, over
+/// an alphabet which may be too large to enumerate, such as [`CharSet::all`].
+///
+/// Reserved chars in the alphabet can not be written in the expression, but can still be
+/// matched by a class, such as `\p{P}` for punctuation.
+pub fn generate_range_dfa(
+    raw_expression: &str,
+    alphabet: &CharSet,
+) -> Result<RangeDfa, DfaGenerationError> {
+    generate_range_dfa_with_options(raw_expression, alphabet, &GenerationOptions::default())
+}
+
+/// Generates a DFA with range-based transitions from an input regular expression string, over
+/// an alphabet which may be too large to enumerate, as controlled by the given options.
+pub fn generate_range_dfa_with_options(
+    raw_expression: &str,
+    alphabet: &CharSet,
+    options: &GenerationOptions,
+) -> Result<RangeDfa, DfaGenerationError> {
+    let lexed_expression = lexer::lex_string_over_set(alphabet, raw_expression)?;
+    let parsed_expression = parser::parse(lexed_expression)?;
+    let annotated_expression = annotator::annotate_ast(options.apply(parsed_expression, alphabet))?;
+    Ok(range_dfa::generate_range_dfa(
+        annotated_expression,
+        alphabet.clone(),
+    ))
+}
+
+/// Generates a DFA from an input regular expression string and alphabet, as controlled by the
+/// given options, along with a [`Trace`] of every step of its construction.
+pub fn generate_dfa_with_trace(
+    raw_expression: &str,
+    alphabet: &str,
+    options: &GenerationOptions,
+) -> Result<(Dfa, Trace), DfaGenerationError> {
+    let construction = explain_with_options(raw_expression, alphabet, options)?;
+    Ok((construction.dfa, construction.trace))
+}
+
+/// Generates a DFA from an input regular expression string and alphabet, reporting the
+/// intermediate results of the construction.
+///
+/// See [`Construction`] for what is reported.
+pub fn explain(raw_expression: &str, alphabet: &str) -> Result<Construction, DfaGenerationError> {
+    explain_with_options(raw_expression, alphabet, &GenerationOptions::default())
+}
+
+/// Generates a DFA from an input regular expression string and alphabet, as controlled by the
+/// given options, reporting the intermediate results of the construction.
+///
+/// The reported nodes and positions are those of the expression after the options have been
+/// applied, so a case-insensitive char is reported as a class.
+pub fn explain_with_options(
+    raw_expression: &str,
+    alphabet: &str,
+    options: &GenerationOptions,
+) -> Result<Construction, DfaGenerationError> {
     let sanitised_alphabet = lexer::generate_token_map(alphabet)?;
     let lexed_expression = lexer::lex_string(&sanitised_alphabet, raw_expression)?;
     let parsed_expression = parser::parse(lexed_expression)?;
-    let annotated_expression = annotator::annotate_ast(parsed_expression)?;
-    Ok(dfa::generate_dfa(annotated_expression))
+    let alphabet = sanitised_alphabet.alphabet();
+    let annotated_expression = annotator::annotate_ast(
+        options.apply(parsed_expression, &alphabet.iter().copied().collect()),
+    )?;
+    Ok(Construction::new(annotated_expression, alphabet))
 }
@@ -0,0 +1,202 @@
+//! Matches input against a [`Dfa`] incrementally, allowing input to arrive in chunks.
+
+use crate::dfa::Dfa;
+use crate::utf8::{InvalidEncodingError, Utf8Decoder};
+use std::cell::OnceCell;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::{ErrorKind, Read};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of bytes requested from a reader at a time by [`Dfa::accepts_reader`].
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// Wraps all errors which can occur while matching input from a reader.
+#[derive(Debug)]
+pub enum ReadError {
+    /// Represents an error returned by the reader itself.
+    Io(std::io::Error),
+    /// Represents that the bytes read were not valid UTF-8.
+    InvalidEncoding(InvalidEncodingError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ReadError::Io(e) => write!(f, "Failed to read input: {}", e),
+            ReadError::InvalidEncoding(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for ReadError {
+    fn from(value: std::io::Error) -> Self {
+        ReadError::Io(value)
+    }
+}
+
+impl From<InvalidEncodingError> for ReadError {
+    fn from(value: InvalidEncodingError) -> Self {
+        ReadError::InvalidEncoding(value)
+    }
+}
+
+/// Holds the current state of a [`Dfa`] over input which is fed to it in chunks.
+///
+/// Chunk boundaries may fall anywhere in the input, including part way through the UTF-8
+/// encoding of a char passed to [`Matcher::feed_bytes`].
+#[derive(Debug)]
+pub struct Matcher<'a> {
+    /// The DFA being matched against.
+    dfa: &'a Dfa,
+    /// The current state of the DFA, or [`None`] if the input consumed so far has no
+    /// transition, in which case no continuation of the input can be accepted.
+    state: Option<usize>,
+    /// The states of the DFA from which an accepting state can be reached, found when first
+    /// needed by [`Matcher::is_dead`].
+    live_states: OnceCell<HashSet<usize>>,
+    /// Decodes the input to [`Matcher::feed_bytes`], holding any char whose encoding has not
+    /// yet been fully fed.
+    decoder: Utf8Decoder,
+}
+
+impl<'a> Matcher<'a> {
+    /// Creates a matcher at the start state of the DFA.
+    pub fn new(dfa: &'a Dfa) -> Matcher<'a> {
+        Matcher {
+            dfa,
+            state: Some(dfa.start_state),
+            live_states: OnceCell::new(),
+            decoder: Utf8Decoder::new(),
+        }
+    }
+
+    /// Advances the matcher by a single char.
+    fn step(&mut self, c: char) {
+        self.state = self.state.and_then(|state| self.dfa.next_state(state, c));
+    }
+
+    /// Feeds a chunk of input to the matcher.
+    ///
+    /// Any bytes left pending by [`Matcher::feed_bytes`] are an incomplete char, so are
+    /// treated as invalid input.
+    pub fn feed(&mut self, input: &str) {
+        if self.decoder.finish().is_err() {
+            self.state = None;
+        }
+        for c in input.chars() {
+            if self.state.is_none() {
+                return;
+            }
+            self.step(c);
+        }
+    }
+
+    /// Feeds a chunk of UTF-8 encoded input to the matcher.
+    ///
+    /// If the chunk ends part way through the encoding of a char, the remaining bytes are
+    /// expected at the start of the next chunk. Input which is not valid UTF-8 leaves the
+    /// matcher dead.
+    pub fn feed_bytes(&mut self, input: &[u8]) {
+        for byte in input {
+            if self.state.is_none() {
+                return;
+            }
+            match self.decoder.push(*byte) {
+                Ok(Some(c)) => self.step(c),
+                Ok(None) => continue,
+                Err(_) => self.state = None,
+            }
+        }
+    }
+
+    /// Returns whether the DFA accepts the input fed so far.
+    pub fn is_accepting(&self) -> bool {
+        match self.state {
+            Some(state) => {
+                !self.decoder.is_incomplete() && self.dfa.accepting_states.contains(&state)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether neither the input fed so far nor any continuation of it can be
+    /// accepted, either because it has left the DFA or because it has reached a state from
+    /// which no accepting state can be reached.
+    pub fn is_dead(&self) -> bool {
+        self.state.is_none_or(|state| {
+            !self
+                .live_states
+                .get_or_init(|| self.dfa.live_states())
+                .contains(&state)
+        })
+    }
+
+    /// Returns the matcher to the start state of the DFA, discarding all input fed so far.
+    pub fn reset(&mut self) {
+        self.state = Some(self.dfa.start_state);
+        self.decoder = Utf8Decoder::new();
+    }
+}
+
+impl Dfa {
+    /// Creates a [`Matcher`] at the start state of the DFA.
+    pub fn matcher(&self) -> Matcher<'_> {
+        Matcher::new(self)
+    }
+
+    /// Returns whether the DFA accepts the input string.
+    pub fn accepts(&self, input: &str) -> bool {
+        self.accepts_iter(input.chars())
+    }
+
+    /// Returns whether the DFA accepts the string made up of the chars of the input.
+    ///
+    /// The input is consumed only until the DFA rejects it.
+    pub fn accepts_iter<I>(&self, input: I) -> bool
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut matcher = self.matcher();
+        for c in input {
+            matcher.step(c);
+            if matcher.state.is_none() {
+                return false;
+            }
+        }
+        matcher.is_accepting()
+    }
+
+    /// Returns whether the DFA accepts the UTF-8 encoded contents of the reader.
+    ///
+    /// The reader is consumed only until the DFA rejects the input, so any invalid encoding
+    /// after that point is not reported.
+    pub fn accepts_reader<R>(&self, mut reader: R) -> Result<bool, ReadError>
+    where
+        R: Read,
+    {
+        let mut matcher = self.matcher();
+        let mut buffer = [0; READ_CHUNK_SIZE];
+        loop {
+            let n_read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(ReadError::Io(e)),
+            };
+            for byte in &buffer[..n_read] {
+                if let Some(c) = matcher.decoder.push(*byte)? {
+                    matcher.step(c);
+                    if matcher.state.is_none() {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        matcher.decoder.finish()?;
+        Ok(matcher.is_accepting())
+    }
+}
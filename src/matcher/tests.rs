@@ -0,0 +1,144 @@
+use super::*;
+use crate::generate_dfa;
+
+#[test]
+fn test_feed_across_chunks() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap();
+    let mut matcher = dfa.matcher();
+    assert!(!matcher.is_accepting());
+    matcher.feed("ba");
+    assert!(!matcher.is_accepting());
+    matcher.feed("b");
+    matcher.feed("");
+    matcher.feed("b");
+    assert!(matcher.is_accepting());
+    matcher.feed("a");
+    assert!(!matcher.is_accepting());
+    assert!(!matcher.is_dead());
+}
+
+#[test]
+fn test_dead_matcher_stays_dead() {
+    let dfa = generate_dfa("ab", "abc").unwrap();
+    let mut matcher = dfa.matcher();
+    matcher.feed("ac");
+    assert!(matcher.is_dead());
+    matcher.feed("b");
+    assert!(matcher.is_dead());
+    assert!(!matcher.is_accepting());
+}
+
+#[test]
+fn test_dead_in_state_which_can_not_accept() {
+    // After "a", the start anchor can never hold, so no continuation is accepted even though
+    // the DFA has a state for the input.
+    let dfa = generate_dfa("a^b|b", "ab").unwrap();
+    let mut matcher = dfa.matcher();
+    assert!(!matcher.is_dead());
+    matcher.feed("a");
+    assert!(dfa.next_state(dfa.start_state, 'a').is_some());
+    assert!(matcher.is_dead());
+    assert!(!matcher.is_accepting());
+    matcher.reset();
+    matcher.feed("b");
+    assert!(!matcher.is_dead());
+    assert!(matcher.is_accepting());
+}
+
+#[test]
+fn test_reset() {
+    let dfa = generate_dfa("ab", "ab").unwrap();
+    let mut matcher = dfa.matcher();
+    matcher.feed("b");
+    assert!(matcher.is_dead());
+    matcher.reset();
+    assert!(!matcher.is_dead());
+    matcher.feed("ab");
+    assert!(matcher.is_accepting());
+}
+
+#[test]
+fn test_feed_bytes_split_within_char() {
+    let dfa = generate_dfa("a🦀*", "a🦀").unwrap();
+    let input = "a🦀🦀".as_bytes();
+    for split in 0..=input.len() {
+        let mut matcher = dfa.matcher();
+        matcher.feed_bytes(&input[..split]);
+        matcher.feed_bytes(&input[split..]);
+        assert!(matcher.is_accepting());
+    }
+    let mut matcher = dfa.matcher();
+    matcher.feed_bytes(&input[..3]);
+    assert!(!matcher.is_accepting());
+    assert!(!matcher.is_dead());
+}
+
+#[test]
+fn test_feed_bytes_invalid_utf8() {
+    let dfa = generate_dfa("a*", "a").unwrap();
+    let mut matcher = dfa.matcher();
+    matcher.feed_bytes(&[b'a', 0xff]);
+    assert!(matcher.is_dead());
+    matcher.reset();
+    matcher.feed_bytes(&[0xed, 0xa0, 0x80]); // an encoded surrogate
+    assert!(matcher.is_dead());
+}
+
+#[test]
+fn test_feed_after_incomplete_bytes() {
+    let dfa = generate_dfa("é*", "é").unwrap();
+    let mut matcher = dfa.matcher();
+    matcher.feed_bytes(&"é".as_bytes()[..1]);
+    matcher.feed("é");
+    assert!(matcher.is_dead());
+}
+
+#[test]
+fn test_accepts_iter() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap();
+    assert!(dfa.accepts_iter("aabb".chars()));
+    assert!(dfa.accepts_iter(vec!['a', 'b', 'b']));
+    assert!(!dfa.accepts_iter("aaaaa".chars()));
+    assert!(dfa.accepts("babb"));
+    assert!(!dfa.accepts("bab"));
+}
+
+#[test]
+fn test_accepts_iter_stops_at_rejection() {
+    let dfa = generate_dfa("ab", "ab").unwrap();
+    assert!(!dfa.accepts_iter(std::iter::repeat('b')));
+}
+
+#[test]
+fn test_accepts_reader() {
+    let dfa = generate_dfa("a🦀*", "a🦀").unwrap();
+    assert!(dfa.accepts_reader("a🦀🦀".as_bytes()).unwrap());
+    assert!(!dfa.accepts_reader("🦀".as_bytes()).unwrap());
+    let long_input = format!("a{}", "🦀".repeat(10000));
+    assert!(dfa.accepts_reader(long_input.as_bytes()).unwrap());
+}
+
+#[test]
+fn test_accepts_reader_invalid_encoding() {
+    let dfa = generate_dfa("a*", "a").unwrap();
+    let output = dfa.accepts_reader(&[b'a', b'a', 0xc3][..]).unwrap_err();
+    match output {
+        ReadError::InvalidEncoding(e) => assert_eq!(e, InvalidEncodingError { offset: 2 }),
+        e => panic!("Expected ReadError::InvalidEncoding; Got {:?}.", e),
+    }
+}
+
+#[test]
+fn test_accepts_reader_io_error() {
+    struct FailingReader;
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("failed"))
+        }
+    }
+    let dfa = generate_dfa("a*", "a").unwrap();
+    assert!(matches!(
+        dfa.accepts_reader(FailingReader),
+        Err(ReadError::Io(_))
+    ));
+}
@@ -0,0 +1,84 @@
+//! Minimizes a [`Dfa`] by partition refinement.
+
+use crate::dfa::Dfa;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[cfg(test)]
+mod tests;
+
+impl Dfa {
+    /// Returns the DFA with the fewest states which accepts the same language.
+    ///
+    /// Unreachable states, and states from which no accepting state can be reached, are
+    /// removed, so the result never contains a state which rejects every input. States are
+    /// then merged using Moore's partition refinement, starting from the partition into
+    /// accepting and non-accepting states. The result is canonical, as described in
+    /// [`Dfa::canonicalize`].
+    pub fn minimize(&self) -> Dfa {
+        let live_states = self.live_states();
+        let useful_states = self.useful_states();
+        if useful_states.is_empty() {
+            return Dfa {
+                n_states: 1,
+                start_state: 0,
+                accepting_states: HashSet::new(),
+                transition_function: HashMap::new(),
+                alphabet: self.alphabet.clone(),
+            };
+        }
+        let symbols = self.symbols();
+        let useful_target = |state: usize, c: char| {
+            self.next_state(state, c)
+                .filter(|target_state| live_states.contains(target_state))
+        };
+        let mut block_of: HashMap<usize, usize> = useful_states
+            .iter()
+            .map(|state| (*state, usize::from(!self.accepting_states.contains(state))))
+            .collect();
+        let mut n_blocks = block_of.values().collect::<BTreeSet<_>>().len();
+        loop {
+            let mut signatures: HashMap<(usize, Vec<Option<usize>>), usize> = HashMap::new();
+            let mut next_block_of = HashMap::new();
+            for state in &useful_states {
+                let signature = (
+                    block_of[state],
+                    symbols
+                        .iter()
+                        .map(|c| useful_target(*state, *c).map(|t| block_of[&t]))
+                        .collect(),
+                );
+                let n_signatures = signatures.len();
+                let block = *signatures.entry(signature).or_insert(n_signatures);
+                next_block_of.insert(*state, block);
+            }
+            block_of = next_block_of;
+            if signatures.len() == n_blocks {
+                break;
+            }
+            n_blocks = signatures.len();
+        }
+        let mut minimized = Dfa {
+            n_states: n_blocks,
+            start_state: block_of[&self.start_state],
+            accepting_states: HashSet::new(),
+            transition_function: HashMap::new(),
+            alphabet: self.alphabet.clone(),
+        };
+        for state in &useful_states {
+            let block = block_of[state];
+            if self.accepting_states.contains(state) {
+                minimized.accepting_states.insert(block);
+            }
+            for c in &symbols {
+                if let Some(target_state) = useful_target(*state, *c) {
+                    minimized
+                        .transition_function
+                        .entry(block)
+                        .or_default()
+                        .insert(*c, block_of[&target_state]);
+                }
+            }
+        }
+        minimized.canonicalize()
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+use crate::generate_dfa;
+
+#[test]
+fn test_minimize_merges_equivalent_states() {
+    // "a|b" gives states for each of the two chars which only differ by their position sets.
+    let dfa = generate_dfa("aa|ba", "ab").unwrap();
+    assert_eq!(dfa.n_states, 4);
+    let expected_output = Dfa {
+        n_states: 3,
+        start_state: 0,
+        accepting_states: HashSet::from([2]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([('a', 1), ('b', 1)])),
+            (1, HashMap::from([('a', 2)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert_eq!(dfa.minimize(), expected_output);
+}
+
+#[test]
+fn test_minimize_is_idempotent() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap().minimize();
+    assert_eq!(dfa.n_states, 4);
+    assert_eq!(dfa.minimize(), dfa);
+}
+
+#[test]
+fn test_minimize_removes_dead_and_unreachable_states() {
+    let dfa = Dfa {
+        n_states: 4,
+        start_state: 0,
+        accepting_states: HashSet::from([1]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([('a', 1), ('b', 2)])),
+            (2, HashMap::from([('a', 2)])),
+            (3, HashMap::from([('a', 1)])),
+        ]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    let expected_output = Dfa {
+        n_states: 2,
+        start_state: 0,
+        accepting_states: HashSet::from([1]),
+        transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert_eq!(dfa.minimize(), expected_output);
+}
+
+#[test]
+fn test_minimize_empty_language() {
+    let dfa = Dfa {
+        n_states: 2,
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    let expected_output = Dfa {
+        n_states: 1,
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transition_function: HashMap::new(),
+        alphabet: BTreeSet::from(['a', 'b']),
+    };
+    assert_eq!(dfa.minimize(), expected_output);
+}
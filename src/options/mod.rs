@@ -0,0 +1,102 @@
+//! Options controlling how an [`Expression`] is turned into a DFA.
+
+use crate::char_set::CharSet;
+use crate::parser::Expression;
+use crate::unicode::cased_chars;
+use std::collections::BTreeSet;
+
+#[cfg(test)]
+mod tests;
+
+/// Options controlling how a DFA is generated from an expression.
+///
+/// The default options match each char exactly.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GenerationOptions {
+    pub(crate) case_insensitive: bool,
+}
+
+impl GenerationOptions {
+    /// Returns the default options.
+    pub fn new() -> GenerationOptions {
+        GenerationOptions::default()
+    }
+
+    /// Sets whether each char matches both its lowercase and uppercase forms, where those
+    /// forms are in the alphabet.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> GenerationOptions {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Applies the options to an expression over the given alphabet.
+    pub(crate) fn apply(&self, expression: Expression, alphabet: &CharSet) -> Expression {
+        match self.case_insensitive {
+            true => fold_case(expression, alphabet),
+            false => expression,
+        }
+    }
+}
+
+/// Adds to chars the lowercase and uppercase forms of c which are single chars in the
+/// alphabet, along with c itself.
+fn add_case_variants(c: char, alphabet: &CharSet, chars: &mut BTreeSet<char>) {
+    chars.insert(c);
+    for variant in [
+        c.to_lowercase().collect::<Vec<char>>(),
+        c.to_uppercase().collect(),
+    ] {
+        if let [variant] = variant[..] {
+            if alphabet.contains(variant) {
+                chars.insert(variant);
+            }
+        }
+    }
+}
+
+/// Replaces each char of the expression with a class of its case variants in the alphabet.
+///
+/// A char with no other case in the alphabet is left as it is, so each leaf is still a single
+/// position of the annotated expression.
+fn fold_case(expression: Expression, alphabet: &CharSet) -> Expression {
+    match expression {
+        Expression::Concatenation(sub_expressions) => Expression::Concatenation(
+            sub_expressions
+                .into_iter()
+                .map(|sub_expression| fold_case(sub_expression, alphabet))
+                .collect(),
+        ),
+        Expression::Choice(sub_expressions) => Expression::Choice(
+            sub_expressions
+                .into_iter()
+                .map(|sub_expression| fold_case(sub_expression, alphabet))
+                .collect(),
+        ),
+        Expression::Closure(sub_expression) => {
+            Expression::Closure(Box::from(fold_case(*sub_expression, alphabet)))
+        }
+        Expression::Char(c) => {
+            let mut chars = BTreeSet::new();
+            add_case_variants(c, alphabet, &mut chars);
+            match chars.len() {
+                1 => Expression::Char(c),
+                _ => Expression::Class(chars.into_iter().collect()),
+            }
+        }
+        Expression::Class(chars) => {
+            // Classes may be far larger than the alphabet, so the alphabet is searched for the
+            // case variants of their chars, rather than the other way around. Only cased chars
+            // of the alphabet can be added, which keeps the search small for large alphabets.
+            let variants: CharSet = alphabet
+                .intersection(cased_chars())
+                .chars()
+                .filter(|c| {
+                    let mut variants = c.to_lowercase().chain(c.to_uppercase());
+                    variants.any(|variant| chars.contains(variant))
+                })
+                .collect();
+            Expression::Class(chars.union(&variants))
+        }
+        expression => expression,
+    }
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::trace::TraceEvent;
+use crate::{generate_dfa_with_options, generate_dfa_with_trace, Regex};
+
+#[test]
+fn test_fold_case_only_uses_chars_in_alphabet() {
+    let alphabet: CharSet = ['a', 'A', 'b', '1'].into_iter().collect();
+    let expression = Expression::Concatenation(vec![
+        Expression::Char('a'),
+        Expression::Char('b'),
+        Expression::Char('1'),
+    ]);
+    let expected_output = Expression::Concatenation(vec![
+        Expression::Class(CharSet::from_ranges(['A'..='A', 'a'..='a'])),
+        Expression::Char('b'),
+        Expression::Char('1'),
+    ]);
+    assert_eq!(fold_case(expression, &alphabet), expected_output);
+}
+
+#[test]
+fn test_case_insensitive_dfa() {
+    let options = GenerationOptions::new().case_insensitive(true);
+    let dfa = generate_dfa_with_options("ab*", "abAB", &options).unwrap();
+    for accepted in ["a", "A", "aBb", "AbB"] {
+        assert!(dfa.accepts(accepted), "{}", accepted);
+    }
+    assert!(!dfa.accepts("b"));
+    // Each char is a single position, so there are no more states than without the option.
+    let exact_dfa = generate_dfa_with_options("ab*", "abAB", &GenerationOptions::new()).unwrap();
+    assert_eq!(dfa.n_states, exact_dfa.n_states);
+}
+
+#[test]
+fn test_case_insensitive_regex() {
+    let options = GenerationOptions::new().case_insensitive(true);
+    let dfa = Regex::sym('X')
+        .then(Regex::sym('y'))
+        .to_dfa_with_options("xY", &options)
+        .unwrap();
+    for accepted in ["xy", "Xy", "xY", "XY"] {
+        assert!(dfa.accepts(accepted), "{}", accepted);
+    }
+}
+
+#[test]
+fn test_case_sensitive_by_default() {
+    let dfa = generate_dfa_with_options("a", "aA", &GenerationOptions::default()).unwrap();
+    assert!(!dfa.accepts("A"));
+}
+
+#[test]
+fn test_case_insensitive_property() {
+    let options = GenerationOptions::new().case_insensitive(true);
+    let dfa = generate_dfa_with_options("\\p{Lu}", "aA1", &options).unwrap();
+    assert!(dfa.accepts("a"));
+    assert!(dfa.accepts("A"));
+    assert!(!dfa.accepts("1"));
+}
+
+#[test]
+fn test_case_insensitive_trace() {
+    let options = GenerationOptions::new().case_insensitive(true);
+    let (dfa, trace) = generate_dfa_with_trace("a", "aA", &options).unwrap();
+    assert!(dfa.accepts("A"));
+    let transitions: Vec<char> = trace
+        .events()
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::TransitionAdded { c, .. } => Some(*c),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(transitions, ['A', 'a']);
+}
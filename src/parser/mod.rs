@@ -1,7 +1,9 @@
 //! Generates an AST of [`Expression`] nodes from a [`Token`] stream.
 
+use crate::char_set::CharSet;
 use crate::lexer::ReservedToken;
 use crate::lexer::Token;
+use crate::unicode::{property, property_name};
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::iter::Peekable;
@@ -61,16 +63,161 @@ pub enum SyntacticError {
     MissingExpectedToken(MissingExpectedTokenError),
 }
 
+impl Display for SyntacticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            SyntacticError::UnexpectedToken(e) => write!(f, "{}", e),
+            SyntacticError::MissingExpectedToken(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A zero-width assertion about the chars either side of a position in the input, which matches
+/// the empty string wherever it holds.
+///
+/// The whole input is matched by a DFA, so the start and end are those of the whole input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Assertion {
+    /// Holds at the start of the input.
+    Start,
+    /// Holds at the end of the input.
+    End,
+    /// Holds between a word char and a non-word char, where the start and end of the input
+    /// count as non-word chars. The word chars are the letters, marks, decimal digits and
+    /// connector punctuation, such as `_`.
+    WordBoundary,
+    /// Holds wherever [`Assertion::WordBoundary`] does not.
+    NotWordBoundary,
+}
+
+impl Display for Assertion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Assertion::Start => write!(f, "^"),
+            Assertion::End => write!(f, "$"),
+            Assertion::WordBoundary => write!(f, "\\b"),
+            Assertion::NotWordBoundary => write!(f, "\\B"),
+        }
+    }
+}
+
 /// Represents an AST node.
 ///
-/// [`Expression::EmptyString`] and [`Expression::Char`] are always and the only leaf nodes.
-#[derive(Debug, PartialEq)]
+/// [`Expression::EmptyString`], [`Expression::EmptySet`], [`Expression::Char`],
+/// [`Expression::Class`] and [`Expression::Assertion`] are always and the only leaf nodes.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Expression {
+    /// Matches its sub-expressions one after another.
     Concatenation(Vec<Expression>),
+    /// Matches any one of its sub-expressions.
     Choice(Vec<Expression>),
+    /// Matches its sub-expression zero or more times.
     Closure(Box<Expression>),
+    /// Matches a single char.
     Char(char),
+    /// Matches any single char of a set which is also in the alphabet.
+    Class(CharSet),
+    /// Matches only the empty string.
     EmptyString,
+    /// Matches no strings at all.
+    EmptySet,
+    /// Matches the empty string where the assertion holds.
+    Assertion(Assertion),
+}
+
+impl Expression {
+    /// Returns whether the expression matches the empty string, whatever chars surround it.
+    ///
+    /// An [`Expression::Assertion`] only matches the empty string where it holds, so is not
+    /// nullable.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Expression::Concatenation(sub_expressions) => {
+                sub_expressions.iter().all(Expression::is_nullable)
+            }
+            Expression::Choice(sub_expressions) => {
+                sub_expressions.iter().any(Expression::is_nullable)
+            }
+            Expression::Closure(_) | Expression::EmptyString => true,
+            Expression::Char(_)
+            | Expression::Class(_)
+            | Expression::EmptySet
+            | Expression::Assertion(_) => false,
+        }
+    }
+
+    /// Writes the expression, surrounded by parentheses if parenthesize is true.
+    fn fmt_parenthesized(&self, f: &mut Formatter<'_>, parenthesize: bool) -> std::fmt::Result {
+        match parenthesize {
+            true => write!(f, "({})", self),
+            false => write!(f, "{}", self),
+        }
+    }
+}
+
+/// Writes the expression in the concrete [syntax](crate), such that parsing the result gives
+/// back the same expression.
+///
+/// Parentheses are only written where the precedence of choice, concatenation and closure
+/// requires them, or where the parser would otherwise flatten nested choices or
+/// concatenations. [`Expression::EmptyString`] is written as `()`. Chars are written as they
+/// are, so an expression containing a reserved char cannot be parsed back. An
+/// [`Expression::Class`] of the chars with a Unicode property is written as `\p{Name}`. Any
+/// other class has no syntax of its own, so is written as the equivalent choice between its
+/// chars, which parses back to an equivalent but different expression.
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Choice(sub_expressions) => {
+                for (i, sub_expression) in sub_expressions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    let parenthesize = matches!(sub_expression, Expression::Choice(_));
+                    sub_expression.fmt_parenthesized(f, parenthesize)?;
+                }
+                Ok(())
+            }
+            Expression::Concatenation(sub_expressions) => {
+                for sub_expression in sub_expressions {
+                    let parenthesize = matches!(
+                        sub_expression,
+                        Expression::Choice(_) | Expression::Concatenation(_)
+                    );
+                    sub_expression.fmt_parenthesized(f, parenthesize)?;
+                }
+                Ok(())
+            }
+            Expression::Closure(sub_expression) => {
+                // A class of several chars parenthesizes itself.
+                let parenthesize = !matches!(
+                    sub_expression.as_ref(),
+                    Expression::Char(_)
+                        | Expression::Class(_)
+                        | Expression::EmptyString
+                        | Expression::EmptySet
+                        | Expression::Assertion(_)
+                );
+                sub_expression.fmt_parenthesized(f, parenthesize)?;
+                write!(f, "*")
+            }
+            Expression::Char(c) => write!(f, "{}", c),
+            Expression::Class(chars) => {
+                if let Some(name) = property_name(chars) {
+                    return write!(f, "\\p{{{}}}", name);
+                }
+                let chars: Vec<String> = chars.chars().map(|c| c.to_string()).collect();
+                match chars.len() {
+                    0 => write!(f, "∅"),
+                    1 => write!(f, "{}", chars[0]),
+                    _ => write!(f, "({})", chars.join("|")),
+                }
+            }
+            Expression::EmptyString => write!(f, "()"),
+            Expression::EmptySet => write!(f, "∅"),
+            Expression::Assertion(assertion) => write!(f, "{}", assertion),
+        }
+    }
 }
 
 /// Parses an [`Expression::Char`] or [`Expression`] as defined in the [syntax documentation](crate).
@@ -84,6 +231,29 @@ where
             token_stream.next();
             Ok(Expression::Char(c))
         }
+        Some(Token::ReservedToken(ReservedToken::EmptySet)) => {
+            token_stream.next();
+            Ok(Expression::EmptySet)
+        }
+        Some(Token::ReservedToken(reserved_token @ ReservedToken::StartAnchor))
+        | Some(Token::ReservedToken(reserved_token @ ReservedToken::EndAnchor))
+        | Some(Token::ReservedToken(reserved_token @ ReservedToken::WordBoundary))
+        | Some(Token::ReservedToken(reserved_token @ ReservedToken::NotWordBoundary)) => {
+            let assertion = match reserved_token {
+                ReservedToken::StartAnchor => Assertion::Start,
+                ReservedToken::EndAnchor => Assertion::End,
+                ReservedToken::WordBoundary => Assertion::WordBoundary,
+                _ => Assertion::NotWordBoundary,
+            };
+            token_stream.next();
+            Ok(Expression::Assertion(assertion))
+        }
+        Some(Token::Property(name)) => {
+            // The lexer only produces tokens for known properties.
+            let (_, chars) = property(name).unwrap();
+            token_stream.next();
+            Ok(Expression::Class(chars))
+        }
         Some(Token::ReservedToken(ReservedToken::LeftPrecedence)) => {
             token_stream.next();
             let expression = parse_expression(token_stream)?;
@@ -154,7 +324,14 @@ where
                 return Ok(Expression::Concatenation(concatenation));
             }
             // next closure
-            Token::Char(_) | Token::ReservedToken(ReservedToken::LeftPrecedence) => {
+            Token::Char(_)
+            | Token::Property(_)
+            | Token::ReservedToken(ReservedToken::LeftPrecedence)
+            | Token::ReservedToken(ReservedToken::EmptySet)
+            | Token::ReservedToken(ReservedToken::StartAnchor)
+            | Token::ReservedToken(ReservedToken::EndAnchor)
+            | Token::ReservedToken(ReservedToken::WordBoundary)
+            | Token::ReservedToken(ReservedToken::NotWordBoundary) => {
                 concatenation.push(parse_closure(token_stream)?);
             }
             // invalid
@@ -132,3 +132,113 @@ fn test_choice_on_empty_string() {
     let expected_output = Expression::Choice(vec![Expression::EmptyString, Expression::Char('a')]);
     assert_eq!(parse(test_input).unwrap(), expected_output);
 }
+
+/// Lexes and parses a raw expression over the given alphabet.
+fn parse_str(raw_expression: &str, alphabet: &str) -> Expression {
+    let token_map = crate::lexer::generate_token_map(alphabet).unwrap();
+    parse(crate::lexer::lex_string(&token_map, raw_expression).unwrap()).unwrap()
+}
+
+#[test]
+fn test_display_uses_minimal_parentheses() {
+    let cases = [
+        ("a", "a"),
+        ("", "()"),
+        ("(a)", "a"),
+        ("((ab)|c)", "ab|c"),
+        ("(a|b)c", "(a|b)c"),
+        ("(a*)b", "a*b"),
+        ("(ab)*", "(ab)*"),
+        ("(a|b)*", "(a|b)*"),
+        ("|a", "()|a"),
+        ("()*", "()*"),
+    ];
+    for (raw_expression, expected_output) in cases {
+        let expression = parse_str(raw_expression, "abc");
+        assert_eq!(
+            expression.to_string(),
+            expected_output,
+            "{}",
+            raw_expression
+        );
+    }
+}
+
+#[test]
+fn test_display_keeps_nesting() {
+    let nested_choice = Expression::Choice(vec![
+        Expression::Char('a'),
+        Expression::Choice(vec![Expression::Char('b'), Expression::Char('c')]),
+    ]);
+    assert_eq!(nested_choice.to_string(), "a|(b|c)");
+    let nested_closure = Expression::Closure(Box::from(Expression::Closure(Box::from(
+        Expression::Char('a'),
+    ))));
+    assert_eq!(nested_closure.to_string(), "(a*)*");
+    let class_closure = Expression::Closure(Box::from(Expression::Class("Aa".chars().collect())));
+    assert_eq!(class_closure.to_string(), "(A|a)*");
+    let property_closure = Expression::Closure(Box::from(Expression::Class(
+        CharSet::property("Lu").unwrap(),
+    )));
+    assert_eq!(property_closure.to_string(), "\\p{Lu}*");
+}
+
+#[test]
+fn test_display_round_trip() {
+    let cases = [
+        "a(bc)",
+        "a|(b|c)",
+        "(a|b)*abb",
+        "((a*)*)*",
+        "a()b",
+        "(|a)(b|())c",
+        "((ab)*c|b(a|()))*",
+        "ab|ba|()",
+        "^(\\ba\\B)*$",
+    ];
+    for raw_expression in cases {
+        let expression = parse_str(raw_expression, "abc");
+        assert_eq!(
+            parse_str(&expression.to_string(), "abc"),
+            expression,
+            "{}",
+            raw_expression
+        );
+    }
+}
+
+#[test]
+fn test_empty_set() {
+    let test_input = vec![
+        Token::Char('a'),
+        Token::ReservedToken(ReservedToken::EmptySet),
+        Token::ReservedToken(ReservedToken::Closure),
+    ];
+    let expected_output = Expression::Concatenation(vec![
+        Expression::Char('a'),
+        Expression::Closure(Box::from(Expression::EmptySet)),
+    ]);
+    assert_eq!(parse(test_input).unwrap(), expected_output);
+    assert_eq!(expected_output.to_string(), "a∅*");
+}
+
+#[test]
+fn test_assertions() {
+    let test_input = vec![
+        Token::ReservedToken(ReservedToken::StartAnchor),
+        Token::Char('a'),
+        Token::ReservedToken(ReservedToken::WordBoundary),
+        Token::ReservedToken(ReservedToken::Closure),
+        Token::ReservedToken(ReservedToken::EndAnchor),
+    ];
+    let expected_output = Expression::Concatenation(vec![
+        Expression::Assertion(Assertion::Start),
+        Expression::Char('a'),
+        Expression::Closure(Box::from(Expression::Assertion(Assertion::WordBoundary))),
+        Expression::Assertion(Assertion::End),
+    ]);
+    let output = parse(test_input).unwrap();
+    assert_eq!(output, expected_output);
+    assert_eq!(output.to_string(), "^a\\b*$");
+    assert!(!Expression::Assertion(Assertion::NotWordBoundary).is_nullable());
+}
@@ -0,0 +1,353 @@
+//! Generates a [`RangeDfa`], whose transitions are labelled by ranges of chars, so that its
+//! alphabet can be as large as all of Unicode.
+
+use crate::annotator::{AnnotatedExpressionContext, AnnotatedExpressionType};
+use crate::char_set::{next_char, previous_char, CharSet};
+use crate::dfa::{
+    breadth_first_order, calculate_matches_next, holds, live_states, look_behind, pass_assertions,
+    useful_states, Dfa, Neighbour,
+};
+use crate::unicode::word_chars;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+
+#[cfg(test)]
+mod tests;
+
+/// One past the greatest code point, bounding the last range of a partition.
+const END_OF_CHARS: u32 = char::MAX as u32 + 1;
+
+/// Represents a DFA whose transitions are labelled by ranges of chars rather than single chars.
+///
+/// The size of the DFA depends on the number of distinct ranges the expression distinguishes,
+/// rather than the size of its alphabet, so every char of the alphabet need never be listed.
+#[derive(Debug, PartialEq)]
+pub struct RangeDfa {
+    /// The starting state of the DFA.
+    pub(crate) start_state: usize,
+    /// A set of accepting states, as in [`Dfa`].
+    pub(crate) accepting_states: HashSet<usize>,
+    /// `transitions[s]` lists the ranges of chars leaving state s with the state each reaches,
+    /// sorted by range. No two ranges overlap, and adjacent ranges reaching the same state are
+    /// merged, so each state has exactly one representation. A char in no range implies that
+    /// the DFA rejects any input which follows that path.
+    pub(crate) transitions: Vec<Vec<(RangeInclusive<char>, usize)>>,
+    /// The alphabet the DFA is defined over. Every range labelling a transition is within the
+    /// alphabet, but a char in the alphabet need not label any transition.
+    pub(crate) alphabet: CharSet,
+}
+
+/// Appends a transition on range to target_state, merging it into the last transition if
+/// their ranges are adjacent and they reach the same state.
+fn push_transition(
+    transitions: &mut Vec<(RangeInclusive<char>, usize)>,
+    range: RangeInclusive<char>,
+    target_state: usize,
+) {
+    if let Some((last_range, last_target_state)) = transitions.last_mut() {
+        if *last_target_state == target_state
+            && next_char(*last_range.end()) == Some(*range.start())
+        {
+            *last_range = *last_range.start()..=*range.end();
+            return;
+        }
+    }
+    transitions.push((range, target_state));
+}
+
+/// Refines the union of the labels into sorted, disjoint ranges, such that each char of a
+/// range is contained in the same labels. Each range is returned with the indices of those
+/// labels.
+fn refine_intervals(labels: &[CharSet]) -> Vec<(RangeInclusive<char>, Vec<usize>)> {
+    let mut boundaries = BTreeSet::new();
+    for range in labels.iter().flat_map(|label| label.ranges()) {
+        boundaries.insert(*range.start() as u32);
+        boundaries.insert(next_char(*range.end()).map_or(END_OF_CHARS, |c| c as u32));
+    }
+    let boundaries: Vec<u32> = boundaries.into_iter().collect();
+    boundaries
+        .windows(2)
+        .filter_map(|window| {
+            // Boundaries are always chars, except for the end of the last range.
+            let start = char::from_u32(window[0]).unwrap();
+            let end = char::from_u32(window[1])
+                .and_then(previous_char)
+                .unwrap_or(char::MAX);
+            let containing: Vec<usize> = (0..labels.len())
+                .filter(|i| labels[*i].contains(start))
+                .collect();
+            match containing.is_empty() {
+                true => None,
+                false => Some((start..=end, containing)),
+            }
+        })
+        .collect()
+}
+
+impl RangeDfa {
+    /// The number of states in the DFA. The states of the DFA are thus 0..n_states.
+    pub fn n_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns the alphabet the DFA is defined over.
+    pub fn alphabet(&self) -> &CharSet {
+        &self.alphabet
+    }
+
+    /// Returns the transitions out of state, as sorted, disjoint ranges of chars with the state
+    /// each reaches.
+    pub fn transitions(&self, state: usize) -> &[(RangeInclusive<char>, usize)] {
+        &self.transitions[state]
+    }
+
+    /// Returns the state reached from state on c, or [`None`] if there is no such transition.
+    ///
+    /// The range containing c is found by binary search.
+    pub(crate) fn next_state(&self, state: usize, c: char) -> Option<usize> {
+        let transitions = &self.transitions[state];
+        let i = transitions.partition_point(|(range, _)| *range.end() < c);
+        transitions
+            .get(i)
+            .filter(|(range, _)| range.contains(&c))
+            .map(|(_, target_state)| *target_state)
+    }
+
+    /// Returns whether the DFA accepts the input string.
+    pub fn accepts(&self, input: &str) -> bool {
+        let mut state = self.start_state;
+        for c in input.chars() {
+            match self.next_state(state, c) {
+                Some(next_state) => state = next_state,
+                None => return false,
+            }
+        }
+        self.accepting_states.contains(&state)
+    }
+
+    /// Returns the states which are both reachable from the start state and can reach some
+    /// accepting state, in ascending order.
+    fn useful_states(&self) -> Vec<usize> {
+        let reachable = breadth_first_order(self.start_state, |state| self.successors(state));
+        let live = live_states(
+            &self.accepting_states,
+            (0..self.n_states())
+                .flat_map(|state| self.successors(state).map(move |target| (state, target))),
+        );
+        useful_states(&reachable.into_iter().collect(), &live)
+    }
+
+    /// Returns the states reached by the transitions out of state, in char order.
+    fn successors(&self, state: usize) -> impl Iterator<Item = usize> + '_ {
+        self.transitions[state]
+            .iter()
+            .map(|(_, target_state)| *target_state)
+    }
+
+    /// Returns the DFA with the states renumbered by a breadth-first search from the start
+    /// state in char order, dropping unreachable states.
+    fn canonicalize(&self) -> RangeDfa {
+        let order = breadth_first_order(self.start_state, |state| self.successors(state));
+        let state_indices: HashMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(index, state)| (*state, index))
+            .collect();
+        RangeDfa {
+            start_state: 0,
+            accepting_states: order
+                .iter()
+                .filter(|state| self.accepting_states.contains(state))
+                .map(|state| state_indices[state])
+                .collect(),
+            transitions: order
+                .iter()
+                .map(|state| {
+                    let mut transitions = Vec::new();
+                    for (range, target_state) in &self.transitions[*state] {
+                        push_transition(
+                            &mut transitions,
+                            range.clone(),
+                            state_indices[target_state],
+                        );
+                    }
+                    transitions
+                })
+                .collect(),
+            alphabet: self.alphabet.clone(),
+        }
+    }
+
+    /// Returns the DFA with the fewest states which accepts the same language.
+    ///
+    /// As in [`Dfa::minimize`], useless states are removed and the rest are merged by Moore's
+    /// partition refinement. The signature of a state maps its ranges to the blocks they reach,
+    /// merging adjacent ranges which reach the same block, so states are compared by their
+    /// ranges without enumerating the chars of any of them.
+    pub fn minimize(&self) -> RangeDfa {
+        let useful_states = self.useful_states();
+        if useful_states.is_empty() {
+            return RangeDfa {
+                start_state: 0,
+                accepting_states: HashSet::new(),
+                transitions: vec![Vec::new()],
+                alphabet: self.alphabet.clone(),
+            };
+        }
+        let mut block_of: HashMap<usize, usize> = useful_states
+            .iter()
+            .map(|state| (*state, usize::from(!self.accepting_states.contains(state))))
+            .collect();
+        let signature = |state: usize, block_of: &HashMap<usize, usize>| {
+            let mut signature = Vec::new();
+            for (range, target_state) in &self.transitions[state] {
+                if let Some(block) = block_of.get(target_state) {
+                    push_transition(&mut signature, range.clone(), *block);
+                }
+            }
+            signature
+        };
+        let mut n_blocks = block_of.values().collect::<BTreeSet<_>>().len();
+        loop {
+            let mut signatures = HashMap::new();
+            let mut next_block_of = HashMap::new();
+            for state in &useful_states {
+                let n_signatures = signatures.len();
+                let block = *signatures
+                    .entry((block_of[state], signature(*state, &block_of)))
+                    .or_insert(n_signatures);
+                next_block_of.insert(*state, block);
+            }
+            block_of = next_block_of;
+            if signatures.len() == n_blocks {
+                break;
+            }
+            n_blocks = signatures.len();
+        }
+        let mut minimized = RangeDfa {
+            start_state: block_of[&self.start_state],
+            accepting_states: HashSet::new(),
+            transitions: vec![Vec::new(); n_blocks],
+            alphabet: self.alphabet.clone(),
+        };
+        for state in &useful_states {
+            let block = block_of[state];
+            if self.accepting_states.contains(state) {
+                minimized.accepting_states.insert(block);
+            }
+            // Every state of a block has the same signature, so any one of them will do.
+            minimized.transitions[block] = signature(*state, &block_of);
+        }
+        minimized.canonicalize()
+    }
+}
+
+impl Dfa {
+    /// Converts the DFA into a [`RangeDfa`] with the same states, merging transitions on
+    /// adjacent chars which reach the same state into ranges.
+    pub fn to_range_dfa(&self) -> RangeDfa {
+        let transitions = (0..self.n_states)
+            .map(|state| {
+                let mut transitions = Vec::new();
+                for (c, target_state) in self.sorted_transitions(state) {
+                    push_transition(&mut transitions, c..=c, target_state);
+                }
+                transitions
+            })
+            .collect();
+        RangeDfa {
+            start_state: self.start_state,
+            accepting_states: self.accepting_states.clone(),
+            transitions,
+            alphabet: self.alphabet.iter().copied().collect(),
+        }
+    }
+}
+
+/// Generates a [`RangeDfa`] from an input annotated expression with leaf context.
+///
+/// As for a [`Dfa`], each state is a set of positions with a look-behind, and states are
+/// numbered by a breadth-first search in char order. The transitions out of a state are found
+/// by refining the chars of the alphabet matched by its positions, along with the word chars,
+/// into disjoint ranges. Each char of a range is matched by the
+/// same positions, after passing the same assertions, so reaches the same state.
+pub(crate) fn generate_range_dfa(
+    expression: AnnotatedExpressionContext,
+    alphabet: CharSet,
+) -> RangeDfa {
+    let mut matches_next = vec![HashSet::<usize>::new(); expression.leaves.len()];
+    calculate_matches_next(&expression.expression, &mut matches_next);
+    let word_chars = word_chars();
+    let terminal_position = expression.leaves.len() - 1;
+    // Passes the assertions which hold between the look-behind of a state and next.
+    let pass = |(positions, previous): &(BTreeSet<usize>, Option<Neighbour>), next| {
+        pass_assertions(&expression, &matches_next, positions, |assertion| {
+            previous.is_some_and(|previous| holds(assertion, previous, next))
+        })
+    };
+    let initial_positions =
+        BTreeSet::from_iter(expression.expression.matches_start.iter().copied());
+    let initial_look_behind = look_behind(&expression, &initial_positions, Neighbour::Edge);
+    let initial_state = (initial_positions, initial_look_behind);
+    let mut dfa = RangeDfa {
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transitions: Vec::new(),
+        alphabet,
+    };
+    let mut state_indices = HashMap::from([(initial_state.clone(), 0)]);
+    let mut unmarked_states = VecDeque::from([initial_state]);
+    // States are marked in the order they are discovered, so the index of each is the number
+    // of states marked before it.
+    while let Some(unmarked_state) = unmarked_states.pop_front() {
+        let unmarked_state_index = dfa.transitions.len();
+        if pass(&unmarked_state, Neighbour::Edge).contains(&terminal_position) {
+            dfa.accepting_states.insert(unmarked_state_index);
+        }
+        let mut labels = Vec::new();
+        let mut label_positions = Vec::new();
+        // Every position which might be passed to before any char is labelled.
+        let passable_positions =
+            pass_assertions(&expression, &matches_next, &unmarked_state.0, |_| true);
+        for leaf_index in passable_positions {
+            let (label, i) = match &expression.leaves[leaf_index].expression {
+                AnnotatedExpressionType::Char(c, i) => (CharSet::from_iter([*c]), *i),
+                AnnotatedExpressionType::Class(chars, i) => (chars.clone(), *i),
+                _ => continue,
+            };
+            labels.push(label.intersection(&dfa.alphabet));
+            label_positions.push(i);
+        }
+        // No range may contain both word chars and other chars, as they pass different
+        // assertions and leave different look-behinds.
+        labels.push(word_chars.intersection(&dfa.alphabet));
+        let mut transitions = Vec::new();
+        for (range, containing) in refine_intervals(&labels) {
+            let next = Neighbour::of(*range.start(), word_chars);
+            let passed_positions = pass(&unmarked_state, next);
+            let target_positions: BTreeSet<usize> = containing
+                .iter()
+                .filter_map(|label| label_positions.get(*label))
+                .filter(|i| passed_positions.contains(*i))
+                .flat_map(|i| matches_next[*i].iter().copied())
+                .collect();
+            if target_positions.is_empty() {
+                continue;
+            }
+            let target_look_behind = look_behind(&expression, &target_positions, next);
+            let target_state = (target_positions, target_look_behind);
+            let target_state_index = match state_indices.get(&target_state) {
+                Some(target_state_index) => *target_state_index,
+                None => {
+                    let target_state_index = state_indices.len();
+                    state_indices.insert(target_state.clone(), target_state_index);
+                    unmarked_states.push_back(target_state);
+                    target_state_index
+                }
+            };
+            push_transition(&mut transitions, range, target_state_index);
+        }
+        dfa.transitions.push(transitions);
+    }
+    dfa
+}
@@ -0,0 +1,119 @@
+use super::*;
+use crate::{
+    generate_dfa, generate_dfa_with_options, generate_range_dfa, generate_range_dfa_with_options,
+    GenerationOptions,
+};
+
+#[test]
+fn test_matches_char_dfa_over_small_alphabet() {
+    let cases = [
+        "(a|b)*abb",
+        "a*b*c*",
+        "(ab|ba)*",
+        "",
+        "∅",
+        "a(b|c)",
+        "(^a|b$)*",
+        "(\\ba|\\Bb|c)*",
+    ];
+    let alphabet = CharSet::from_iter("abc".chars());
+    for input_expression in cases {
+        let output = generate_range_dfa(input_expression, &alphabet).unwrap();
+        let expected_output = generate_dfa(input_expression, "abc")
+            .unwrap()
+            .to_range_dfa();
+        assert_eq!(output, expected_output, "{}", input_expression);
+    }
+}
+
+#[test]
+fn test_property_over_all_chars() {
+    let dfa = generate_range_dfa("\\p{Lu}\\p{Ll}*", &CharSet::all()).unwrap();
+    assert_eq!(dfa.n_states(), 2);
+    // Every range of the lowercase letters leads back to the accepting state.
+    let lowercase = CharSet::property("Ll").unwrap();
+    assert_eq!(dfa.transitions(1).len(), lowercase.ranges().count());
+    for accepted in ["Hello", "Σίσυφος", "Ω"] {
+        assert!(dfa.accepts(accepted), "{}", accepted);
+    }
+    for rejected in ["", "hello", "H3llo", "HELLO"] {
+        assert!(!dfa.accepts(rejected), "{}", rejected);
+    }
+}
+
+#[test]
+fn test_reserved_chars_matched_by_class() {
+    let dfa = generate_range_dfa("(\\p{Han}|\\p{P})*", &CharSet::all()).unwrap();
+    assert!(dfa.accepts("漢字(、)*"));
+    assert!(!dfa.accepts("漢a"));
+}
+
+#[test]
+fn test_overlapping_ranges_are_refined() {
+    // The letters of the Greek script overlap both classes, so must be split from the rest.
+    let dfa = generate_range_dfa("\\p{Greek}a|\\p{L}b", &CharSet::all()).unwrap();
+    assert!(dfa.accepts("αa"));
+    assert!(dfa.accepts("αb"));
+    assert!(dfa.accepts("zb"));
+    assert!(!dfa.accepts("za"));
+    assert!(!dfa.accepts("1b"));
+}
+
+#[test]
+fn test_minimize_matches_char_dfa() {
+    let alphabet = CharSet::from_iter("abc".chars());
+    for input_expression in ["(a|b)*abb", "aa|ba", "(a|b)*(a|b)", "a∅"] {
+        let output = generate_range_dfa(input_expression, &alphabet)
+            .unwrap()
+            .minimize();
+        let expected_output = generate_dfa(input_expression, "abc")
+            .unwrap()
+            .minimize()
+            .to_range_dfa();
+        assert_eq!(output, expected_output, "{}", input_expression);
+    }
+}
+
+#[test]
+fn test_minimize_merges_ranges() {
+    let dfa = generate_range_dfa("\\p{L}|\\p{Greek}|\\p{Lu}", &CharSet::all()).unwrap();
+    let minimized = dfa.minimize();
+    assert_eq!(minimized.n_states(), 2);
+    let expected_transitions: Vec<_> = CharSet::property("L")
+        .unwrap()
+        .union(&CharSet::property("Greek").unwrap())
+        .ranges()
+        .map(|range| (range, 1))
+        .collect();
+    assert_eq!(minimized.transitions(0), expected_transitions);
+    assert_eq!(minimized.minimize(), minimized);
+}
+
+#[test]
+fn test_word_boundaries_over_all_chars() {
+    let dfa = generate_range_dfa("(\\b\\p{L}*\\b|\\p{Zs})*", &CharSet::all()).unwrap();
+    assert!(dfa.accepts("Ἀθῆναι και Ρώμη"));
+    assert!(dfa.accepts(""));
+    assert!(!dfa.accepts("Ἀθῆναι,"));
+    // Letters either side of an empty word are not a boundary.
+    let dfa = generate_range_dfa("\\p{L}\\b\\p{L}", &CharSet::all()).unwrap();
+    assert!(dfa.minimize().accepting_states.is_empty());
+}
+
+#[test]
+fn test_case_insensitive() {
+    let options = GenerationOptions::new().case_insensitive(true);
+    let dfa = generate_range_dfa_with_options("σ(ab)*\\p{Lu}", &CharSet::all(), &options).unwrap();
+    for accepted in ["σabX", "ΣAbaBx", "Σε"] {
+        assert!(dfa.accepts(accepted), "{}", accepted);
+    }
+    for rejected in ["σab", "σa1", "ςε"] {
+        assert!(!dfa.accepts(rejected), "{}", rejected);
+    }
+    let expected_output = generate_dfa_with_options("a*b", "aAbB", &options)
+        .unwrap()
+        .to_range_dfa();
+    let alphabet = CharSet::from_iter("aAbB".chars());
+    let output = generate_range_dfa_with_options("a*b", &alphabet, &options).unwrap();
+    assert_eq!(output, expected_output);
+}
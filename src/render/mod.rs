@@ -0,0 +1,117 @@
+//! Renders a [`Dfa`] as a state table or as a Graphviz DOT graph.
+
+use crate::dfa::Dfa;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Write;
+
+#[cfg(test)]
+mod tests;
+
+/// Escapes a string for use inside a double-quoted DOT string.
+fn escape_dot(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Renders the state table of the DFA, with a row per state and a column per char of the
+/// alphabet.
+///
+/// The start state is marked with "->" and accepting states with "*". A "-" marks a missing
+/// transition.
+impl Display for Dfa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let header: Vec<String> = self
+            .alphabet
+            .iter()
+            .map(|c| c.escape_debug().to_string())
+            .collect();
+        let rows: Vec<Vec<String>> = (0..self.n_states)
+            .map(|state| {
+                self.alphabet
+                    .iter()
+                    .map(|c| match self.next_state(state, *c) {
+                        Some(target_state) => target_state.to_string(),
+                        None => String::from("-"),
+                    })
+                    .collect()
+            })
+            .collect();
+        let state_width = self.n_states.saturating_sub(1).to_string().len();
+        let cell_width = header
+            .iter()
+            .chain(rows.iter().flatten())
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(0);
+        write!(f, "   {:>state_width$}", "")?;
+        for symbol in &header {
+            write!(f, "  {:>cell_width$}", symbol)?;
+        }
+        for (state, row) in rows.iter().enumerate() {
+            writeln!(f)?;
+            let start_marker = if state == self.start_state { "->" } else { "" };
+            let accepting_marker = if self.accepting_states.contains(&state) {
+                "*"
+            } else {
+                ""
+            };
+            write!(
+                f,
+                "{:>2}{:>1}{:>state_width$}",
+                start_marker, accepting_marker, state
+            )?;
+            for cell in row {
+                write!(f, "  {:>cell_width$}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Dfa {
+    /// Renders the DFA as a graph in the Graphviz DOT language.
+    ///
+    /// Transitions between the same pair of states are drawn as a single edge, labelled with
+    /// every char of those transitions.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dfa {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=circle];\n");
+        dot.push_str("    start [shape=point];\n");
+        writeln!(dot, "    start -> {};", self.start_state).unwrap();
+        for state in 0..self.n_states {
+            if self.accepting_states.contains(&state) {
+                writeln!(dot, "    {} [shape=doublecircle];", state).unwrap();
+            } else {
+                writeln!(dot, "    {};", state).unwrap();
+            }
+        }
+        for state in 0..self.n_states {
+            let mut edges: BTreeMap<usize, Vec<char>> = BTreeMap::new();
+            for (c, target_state) in self.sorted_transitions(state) {
+                edges.entry(target_state).or_default().push(c);
+            }
+            for (target_state, chars) in edges {
+                let label: Vec<String> = chars.iter().map(|c| escape_dot(&c.to_string())).collect();
+                writeln!(
+                    dot,
+                    "    {} -> {} [label=\"{}\"];",
+                    state,
+                    target_state,
+                    label.join(", ")
+                )
+                .unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{generate_dfa, Regex};
+
+#[test]
+fn test_state_table() {
+    let dfa = generate_dfa("(a|b)*abb", "ab").unwrap();
+    let expected_output = [
+        "      a  b",
+        "-> 0  1  0",
+        "   1  1  2",
+        "   2  1  3",
+        "  *3  1  0",
+    ]
+    .join("\n");
+    assert_eq!(dfa.to_string(), expected_output);
+}
+
+#[test]
+fn test_state_table_missing_transitions() {
+    let dfa = generate_dfa("ab|b", "ab").unwrap().minimize();
+    let expected_output = ["      a  b", "-> 0  1  2", "   1  -  2", "  *2  -  -"].join("\n");
+    assert_eq!(dfa.to_string(), expected_output);
+}
+
+#[test]
+fn test_dot() {
+    let dfa = generate_dfa("a|b", "ab").unwrap();
+    let expected_output = [
+        "digraph dfa {",
+        "    rankdir=LR;",
+        "    node [shape=circle];",
+        "    start [shape=point];",
+        "    start -> 0;",
+        "    0;",
+        "    1 [shape=doublecircle];",
+        "    0 -> 1 [label=\"a, b\"];",
+        "}",
+    ]
+    .join("\n");
+    assert_eq!(dfa.to_dot(), expected_output);
+}
+
+#[test]
+fn test_dot_escapes_labels() {
+    // The backslash is reserved in the string syntax, so can only be a symbol of a built regex.
+    let dfa = Regex::sym('"').or(Regex::sym('\\')).to_dfa("").unwrap();
+    assert!(dfa.to_dot().contains("    0 -> 1 [label=\"\\\", \\\\\"];"));
+}
+
+#[test]
+fn test_state_table_includes_unused_chars() {
+    let dfa = generate_dfa("a", "ab").unwrap();
+    let expected_output = ["      a  b", "-> 0  1  -", "  *1  -  -"].join("\n");
+    assert_eq!(dfa.to_string(), expected_output);
+}
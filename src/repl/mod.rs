@@ -0,0 +1,181 @@
+//! An interactive session for exploring each stage of the regular expression to DFA pipeline.
+//!
+//! The session lives in the library rather than in its binary so that it can show the
+//! intermediate results of the pipeline, which are not part of the public API.
+
+use crate::annotator::annotate_ast;
+use crate::construction::Construction;
+use crate::lexer::{generate_token_map, lex_string, Token};
+use crate::parser::{parse, Expression};
+use crate::DfaGenerationError;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+
+#[cfg(test)]
+mod tests;
+
+const HELP: &str = "\
+Commands:
+    alphabet <chars>    Set the alphabet, e.g. \"alphabet ab\".
+    expr <expression>   Set the expression, e.g. \"expr (a|b)*abb\".
+    test <string>       Print whether the DFA accepts the string.
+    tokens              Print the tokens produced by the lexer.
+    ast                 Print the AST produced by the parser.
+    tree                Print the annotated AST with nullable, firstpos and lastpos.
+    followpos           Print the followpos table of the annotated AST.
+    states              Print the positions represented by each state of the DFA.
+    trace               Print each step of the subset construction of the DFA.
+    dfa                 Print the state table of the DFA.
+    show                Print the current alphabet and expression.
+    help                Print this message.
+    quit                End the session.";
+
+/// The prompt written before each command is read.
+const PROMPT: &str = "> ";
+
+/// Holds the alphabet and expression the session is exploring.
+#[derive(Debug, Default)]
+struct Session {
+    alphabet: String,
+    expression: Option<String>,
+}
+
+/// Describes an error from any stage of the pipeline.
+fn describe_error<E>(error: E) -> String
+where
+    E: Into<DfaGenerationError>,
+{
+    error.into().to_string()
+}
+
+/// Renders an AST with one node per line, indenting children beneath their parent.
+fn render_ast(expression: &Expression, depth: usize, rendered: &mut String) {
+    let indent = "  ".repeat(depth);
+    match expression {
+        Expression::Char(c) => writeln!(rendered, "{}Char {:?}", indent, c).unwrap(),
+        Expression::EmptyString => writeln!(rendered, "{}EmptyString", indent).unwrap(),
+        Expression::EmptySet => writeln!(rendered, "{}EmptySet", indent).unwrap(),
+        Expression::Class(chars) => writeln!(rendered, "{}Class {}", indent, chars).unwrap(),
+        Expression::Assertion(assertion) => {
+            writeln!(rendered, "{}Assertion {}", indent, assertion).unwrap()
+        }
+        Expression::Closure(sub_expression) => {
+            writeln!(rendered, "{}Closure", indent).unwrap();
+            render_ast(sub_expression, depth + 1, rendered);
+        }
+        Expression::Concatenation(sub_expressions) | Expression::Choice(sub_expressions) => {
+            let name = match expression {
+                Expression::Concatenation(_) => "Concatenation",
+                _ => "Choice",
+            };
+            writeln!(rendered, "{}{}", indent, name).unwrap();
+            for sub_expression in sub_expressions {
+                render_ast(sub_expression, depth + 1, rendered);
+            }
+        }
+    }
+}
+
+impl Session {
+    /// Returns the current expression, or an error message if none has been set.
+    fn expression(&self) -> Result<&str, String> {
+        self.expression
+            .as_deref()
+            .ok_or_else(|| String::from("No expression set. Use \"expr <expression>\"."))
+    }
+
+    /// Lexes the current expression.
+    fn tokens(&self) -> Result<Vec<Token>, String> {
+        let token_map = generate_token_map(&self.alphabet).map_err(describe_error)?;
+        lex_string(&token_map, self.expression()?).map_err(describe_error)
+    }
+
+    /// Lexes and parses the current expression.
+    fn ast(&self) -> Result<Expression, String> {
+        parse(self.tokens()?).map_err(describe_error)
+    }
+
+    /// Lexes, parses and annotates the current expression, then constructs its DFA over the
+    /// sanitised alphabet, as [`crate::explain`] does.
+    fn construction(&self) -> Result<Construction, String> {
+        let token_map = generate_token_map(&self.alphabet).map_err(describe_error)?;
+        let annotated = annotate_ast(self.ast()?).map_err(describe_error)?;
+        Ok(Construction::new(annotated, token_map.alphabet()))
+    }
+
+    /// Runs a single command, returning the text to print.
+    fn execute(&mut self, line: &str) -> Result<String, String> {
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "alphabet" => {
+                generate_token_map(argument).map_err(describe_error)?;
+                self.alphabet = String::from(argument);
+                Ok(format!("Alphabet set to {:?}.", argument))
+            }
+            "expr" => {
+                let previous_expression = self.expression.replace(String::from(argument));
+                if let Err(message) = self.ast() {
+                    self.expression = previous_expression;
+                    return Err(message);
+                }
+                Ok(format!("Expression set to {:?}.", argument))
+            }
+            "show" => Ok(format!(
+                "Alphabet: {:?}\nExpression: {}",
+                self.alphabet,
+                match &self.expression {
+                    Some(expression) => format!("{:?}", expression),
+                    None => String::from("(none)"),
+                }
+            )),
+            "tokens" => {
+                let tokens: Vec<String> = self.tokens()?.iter().map(|t| t.to_string()).collect();
+                Ok(tokens.join("\n"))
+            }
+            "ast" => {
+                let mut rendered = String::new();
+                render_ast(&self.ast()?, 0, &mut rendered);
+                Ok(String::from(rendered.trim_end()))
+            }
+            "tree" => Ok(self.construction()?.render_tree()),
+            "followpos" => Ok(self.construction()?.render_followpos()),
+            "states" => Ok(self.construction()?.render_states()),
+            "trace" => Ok(self.construction()?.trace().render_text()),
+            "dfa" => Ok(self.construction()?.dfa().to_string()),
+            "test" => match self.construction()?.dfa().accepts(argument) {
+                true => Ok(format!("{:?} is accepted.", argument)),
+                false => Ok(format!("{:?} is rejected.", argument)),
+            },
+            "help" => Ok(String::from(HELP)),
+            _ => Err(format!(
+                "Unknown command {:?}. Use \"help\" to list commands.",
+                command
+            )),
+        }
+    }
+}
+
+/// Runs an interactive session, reading commands from input until it ends or "quit" is read.
+pub fn run(input: &mut dyn BufRead, output: &mut dyn Write) -> std::io::Result<()> {
+    let mut session = Session::default();
+    writeln!(output, "Type \"help\" to list commands.")?;
+    loop {
+        write!(output, "{}", PROMPT)?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        let line = line.strip_suffix('\n').unwrap_or(&line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match line {
+            "" => continue,
+            "quit" => return Ok(()),
+            _ => match session.execute(line) {
+                Ok(text) => writeln!(output, "{}", text)?,
+                Err(message) => writeln!(output, "Error: {}", message)?,
+            },
+        }
+    }
+}
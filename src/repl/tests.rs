@@ -0,0 +1,117 @@
+use super::*;
+
+fn run_session(commands: &[&str]) -> Vec<String> {
+    let mut session = Session::default();
+    commands
+        .iter()
+        .map(|command| match session.execute(command) {
+            Ok(text) => text,
+            Err(message) => format!("Error: {}", message),
+        })
+        .collect()
+}
+
+#[test]
+fn test_tokens() {
+    let output = run_session(&["alphabet ab", "expr a*|b", "tokens"]);
+    let expected_output = [
+        "Char \"a\"",
+        "Reserved Token Closure \"*\"",
+        "Reserved Token Choice \"|\"",
+        "Char \"b\"",
+    ]
+    .join("\n");
+    assert_eq!(output[2], expected_output);
+}
+
+#[test]
+fn test_ast() {
+    let output = run_session(&["alphabet ab", "expr a*|b", "ast"]);
+    let expected_output = ["Choice", "  Closure", "    Char 'a'", "  Char 'b'"].join("\n");
+    assert_eq!(output[2], expected_output);
+}
+
+#[test]
+fn test_annotated_tree() {
+    let output = run_session(&["alphabet ab", "expr a*b", "tree"]);
+    let expected_output = [
+        "Concatenation  nullable=false firstpos={0, 1} lastpos={2}",
+        "  Concatenation  nullable=false firstpos={0, 1} lastpos={1}",
+        "    Closure  nullable=true firstpos={0} lastpos={0}",
+        "      Char 'a' [0]  nullable=false firstpos={0} lastpos={0}",
+        "    Char 'b' [1]  nullable=false firstpos={1} lastpos={1}",
+        "  Terminal [2]  nullable=false firstpos={2} lastpos={2}",
+    ]
+    .join("\n");
+    assert_eq!(output[2], expected_output);
+}
+
+#[test]
+fn test_followpos() {
+    let output = run_session(&["alphabet ab", "expr (a|b)*abb", "followpos"]);
+    let expected_output = [
+        "position  symbol  followpos",
+        "       0     'a'  {0, 1, 2}",
+        "       1     'b'  {0, 1, 2}",
+        "       2     'a'  {3}",
+        "       3     'b'  {4}",
+        "       4     'b'  {5}",
+        "       5       #  {}",
+    ]
+    .join("\n");
+    assert_eq!(output[2], expected_output);
+}
+
+#[test]
+fn test_test_strings() {
+    let output = run_session(&[
+        "alphabet ab",
+        "expr (a|b)*abb",
+        "test babb",
+        "test ba",
+        "test",
+    ]);
+    assert_eq!(output[2], "\"babb\" is accepted.");
+    assert_eq!(output[3], "\"ba\" is rejected.");
+    assert_eq!(output[4], "\"\" is rejected.");
+}
+
+#[test]
+fn test_invalid_expression_is_not_set() {
+    let output = run_session(&["alphabet ab", "expr a", "expr c", "show"]);
+    assert!(output[2].starts_with("Error: Lexical error:"));
+    assert_eq!(output[3], "Alphabet: \"ab\"\nExpression: \"a\"");
+}
+
+#[test]
+fn test_errors() {
+    let output = run_session(&["tokens", "alphabet a*", "frobnicate"]);
+    assert!(output[0].starts_with("Error: No expression set."));
+    assert!(output[1].starts_with("Error: Lexical error:"));
+    assert!(output[2].starts_with("Error: Unknown command"));
+}
+
+#[test]
+fn test_run() {
+    let mut output = Vec::new();
+    run(
+        &mut "alphabet a\r\n\nexpr a\nquit\ntest a\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    let expected_output = [
+        "Type \"help\" to list commands.",
+        "> Alphabet set to \"a\".",
+        "> > Expression set to \"a\".",
+        "> ",
+    ]
+    .join("\n");
+    assert_eq!(String::from_utf8(output).unwrap(), expected_output);
+}
+
+#[test]
+fn test_states() {
+    let output = run_session(&["alphabet a", "expr a", "states"]);
+    let expected_output = ["state  positions", "    0  {0}", "    1  {1}"].join("\n");
+    assert_eq!(output[2], expected_output);
+}
@@ -0,0 +1,75 @@
+//! Draws accepted strings of a [`Dfa`] uniformly at random.
+
+use crate::big_count::BigCount;
+use crate::dfa::Dfa;
+
+#[cfg(test)]
+mod tests;
+
+/// A source of random numbers, implemented by the caller to supply randomness to
+/// [`Dfa::sample`].
+///
+/// Each call should return 32 bits drawn independently and uniformly at random. Implementing
+/// this for a wrapper around any random number generator allows sampling without this crate
+/// depending on one.
+pub trait RandomSource {
+    /// Returns the next 32 random bits.
+    fn next_u32(&mut self) -> u32;
+}
+
+impl Dfa {
+    /// Returns, for each length k from 0 to max_length, the number of strings of length k
+    /// which lead from each state to an accepting state.
+    fn completion_counts(&self, max_length: usize) -> Vec<Vec<BigCount>> {
+        let mut completion_counts: Vec<Vec<BigCount>> = vec![(0..self.n_states)
+            .map(|state| BigCount::from(u64::from(self.accepting_states.contains(&state))))
+            .collect()];
+        for _ in 0..max_length {
+            let previous = completion_counts.last().unwrap();
+            let counts = (0..self.n_states)
+                .map(|state| {
+                    let mut count = BigCount::zero();
+                    for (_, target_state) in self.sorted_transitions(state) {
+                        count += &previous[target_state];
+                    }
+                    count
+                })
+                .collect();
+            completion_counts.push(counts);
+        }
+        completion_counts
+    }
+
+    /// Returns a string drawn uniformly at random from the accepted strings with the given
+    /// length, or [`None`] if there are none.
+    ///
+    /// Every accepted string of that length is equally likely, unlike the strings produced by
+    /// a random walk through the DFA, which favours strings whose prefixes have few
+    /// continuations. The number of strings completing an accepted string from each state is
+    /// counted first, then each char is chosen with probability proportional to the number of
+    /// accepted strings continuing with it.
+    pub fn sample(&self, length: usize, rng: &mut dyn RandomSource) -> Option<String> {
+        let completion_counts = self.completion_counts(length);
+        let mut state = self.start_state;
+        let n_words = &completion_counts[length][state];
+        if n_words.is_zero() {
+            return None;
+        }
+        let mut chosen = BigCount::random_below(n_words, rng);
+        let mut word = String::new();
+        for k in (0..length).rev() {
+            // chosen indexes the accepted strings beginning with the chars so far, ordered by
+            // their remaining chars.
+            for (c, target_state) in self.sorted_transitions(state) {
+                let count = &completion_counts[k][target_state];
+                if chosen < *count {
+                    word.push(c);
+                    state = target_state;
+                    break;
+                }
+                chosen -= count;
+            }
+        }
+        Some(word)
+    }
+}
@@ -0,0 +1,64 @@
+use super::*;
+use crate::generate_dfa;
+use std::collections::HashMap;
+
+/// A xorshift generator, which is random enough for checking the distribution of samples.
+struct XorShift {
+    state: u32,
+}
+
+impl RandomSource for XorShift {
+    fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+}
+
+#[test]
+fn test_samples_are_accepted_with_the_given_length() {
+    let dfa = generate_dfa("(a|b)*abb(a|c)*", "abc").unwrap();
+    let mut rng = XorShift { state: 1 };
+    for length in 3..10 {
+        for _ in 0..20 {
+            let word = dfa.sample(length, &mut rng).unwrap();
+            assert_eq!(word.chars().count(), length);
+            assert!(dfa.accepts(&word), "{}", word);
+        }
+    }
+}
+
+#[test]
+fn test_sample_without_accepted_strings() {
+    let dfa = generate_dfa("ab|ba|()", "ab").unwrap();
+    let mut rng = XorShift { state: 1 };
+    assert_eq!(dfa.sample(1, &mut rng), None);
+    assert_eq!(dfa.sample(0, &mut rng).as_deref(), Some(""));
+}
+
+#[test]
+fn test_samples_are_uniform() {
+    // A random walk would choose "a" first half of the time, giving "aa" and "ab" a quarter
+    // each, rather than the fifth each of a uniform sample.
+    let dfa = generate_dfa("a(a|b)|bb|bc|ba", "abc").unwrap();
+    let mut rng = XorShift { state: 7 };
+    let mut frequencies: HashMap<String, usize> = HashMap::new();
+    for _ in 0..6000 {
+        *frequencies
+            .entry(dfa.sample(2, &mut rng).unwrap())
+            .or_default() += 1;
+    }
+    assert_eq!(frequencies.len(), 5);
+    for (word, frequency) in frequencies {
+        assert!((1000..1400).contains(&frequency), "{} {}", word, frequency);
+    }
+}
+
+#[test]
+fn test_sample_from_large_language() {
+    let dfa = generate_dfa("(a|b|c)*", "abc").unwrap();
+    let mut rng = XorShift { state: 3 };
+    let word = dfa.sample(100, &mut rng).unwrap();
+    assert_eq!(word.len(), 100);
+}
@@ -0,0 +1,304 @@
+//! Simplifies an [`Expression`] by rewriting it with identities of Kleene algebra.
+
+use crate::parser::Expression;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents an identity used to rewrite an expression into a simpler equivalent one.
+///
+/// E, F and G stand for any expressions, ε for the empty string and ∅ for the empty set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimplificationRule {
+    /// E|(F|G) → E|F|G
+    FlattenChoice,
+    /// E(FG) → EFG
+    FlattenConcatenation,
+    /// A choice or concatenation of a single expression is that expression.
+    UnwrapSingleton,
+    /// E|E → E
+    DuplicateArm,
+    /// ε|E → E, where E matches the empty string.
+    RedundantEmptyString,
+    /// εE → E
+    EmptyStringInConcatenation,
+    /// A concatenation of no expressions is ε.
+    EmptyConcatenation,
+    /// ε* → ε
+    ClosureOfEmptyString,
+    /// E∅ → ∅
+    EmptySetInConcatenation,
+    /// ∅|E → E
+    EmptySetInChoice,
+    /// ∅* → ε
+    ClosureOfEmptySet,
+    /// (E*)* → E*
+    NestedClosure,
+    /// (ε|E)* → E*
+    EmptyStringInClosure,
+    /// (E*|F)* → (E|F)*
+    ClosureInClosedChoice,
+    /// EF|EG → E(F|G)
+    CommonPrefix,
+    /// FE|GE → (F|G)E
+    CommonSuffix,
+}
+
+impl Display for SimplificationRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let identity = match self {
+            SimplificationRule::FlattenChoice => "E|(F|G) → E|F|G",
+            SimplificationRule::FlattenConcatenation => "E(FG) → EFG",
+            SimplificationRule::UnwrapSingleton => "(E) → E",
+            SimplificationRule::DuplicateArm => "E|E → E",
+            SimplificationRule::RedundantEmptyString => "ε|E → E, where E is nullable",
+            SimplificationRule::EmptyStringInConcatenation => "εE → E",
+            SimplificationRule::EmptyConcatenation => "concatenation of nothing → ε",
+            SimplificationRule::ClosureOfEmptyString => "ε* → ε",
+            SimplificationRule::EmptySetInConcatenation => "E∅ → ∅",
+            SimplificationRule::EmptySetInChoice => "∅|E → E",
+            SimplificationRule::ClosureOfEmptySet => "∅* → ε",
+            SimplificationRule::NestedClosure => "(E*)* → E*",
+            SimplificationRule::EmptyStringInClosure => "(ε|E)* → E*",
+            SimplificationRule::ClosureInClosedChoice => "(E*|F)* → (E|F)*",
+            SimplificationRule::CommonPrefix => "EF|EG → E(F|G)",
+            SimplificationRule::CommonSuffix => "FE|GE → (F|G)E",
+        };
+        write!(f, "{}", identity)
+    }
+}
+
+/// Represents the result of simplifying an expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simplification {
+    pub(crate) expression: Expression,
+    pub(crate) rules: Vec<SimplificationRule>,
+}
+
+impl Simplification {
+    /// The simplified expression.
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// The rules which were applied, in the order they were applied. A rule is listed once for
+    /// each time it was applied.
+    pub fn rules(&self) -> &[SimplificationRule] {
+        &self.rules
+    }
+
+    /// Consumes the simplification, returning the simplified expression.
+    pub fn into_expression(self) -> Expression {
+        self.expression
+    }
+}
+
+/// Returns the first factor of an expression: the first sub-expression of a concatenation,
+/// or else the expression itself.
+fn first_factor(expression: &Expression) -> &Expression {
+    match expression {
+        Expression::Concatenation(sub_expressions) if !sub_expressions.is_empty() => {
+            &sub_expressions[0]
+        }
+        expression => expression,
+    }
+}
+
+/// Returns the last factor of an expression: the last sub-expression of a concatenation, or
+/// else the expression itself.
+fn last_factor(expression: &Expression) -> &Expression {
+    match expression {
+        Expression::Concatenation(sub_expressions) if !sub_expressions.is_empty() => {
+            &sub_expressions[sub_expressions.len() - 1]
+        }
+        expression => expression,
+    }
+}
+
+/// Returns what remains of an expression after removing the given number of factors from
+/// its start and its end, where the factors are those of [`first_factor`] and
+/// [`last_factor`].
+fn without_factors(expression: &Expression, n_start: usize, n_end: usize) -> Expression {
+    let factors = match expression {
+        Expression::Concatenation(sub_expressions) => sub_expressions.as_slice(),
+        expression => std::slice::from_ref(expression),
+    };
+    let mut remaining = factors[n_start..factors.len() - n_end].to_vec();
+    match remaining.len() {
+        0 => Expression::EmptyString,
+        1 => remaining.pop().unwrap(),
+        _ => Expression::Concatenation(remaining),
+    }
+}
+
+/// Factors out a prefix or suffix shared by two or more arms of a choice, if there is one.
+///
+/// Only the first such group of arms is factored, replacing the first arm of the group.
+fn factor_arms(arms: &mut Vec<Expression>, rules: &mut Vec<SimplificationRule>) {
+    for (rule, factor) in [
+        (
+            SimplificationRule::CommonPrefix,
+            first_factor as fn(&Expression) -> &Expression,
+        ),
+        (SimplificationRule::CommonSuffix, last_factor),
+    ] {
+        for i in 0..arms.len() {
+            let shared = factor(&arms[i]).clone();
+            let group: Vec<usize> = (i..arms.len())
+                .filter(|j| factor(&arms[*j]) == &shared)
+                .collect();
+            if group.len() < 2 {
+                continue;
+            }
+            let rests: Vec<Expression> = group
+                .iter()
+                .map(|j| match rule {
+                    SimplificationRule::CommonPrefix => without_factors(&arms[*j], 1, 0),
+                    _ => without_factors(&arms[*j], 0, 1),
+                })
+                .collect();
+            let rest = Expression::Choice(rests);
+            arms[i] = match rule {
+                SimplificationRule::CommonPrefix => Expression::Concatenation(vec![shared, rest]),
+                _ => Expression::Concatenation(vec![rest, shared]),
+            };
+            for j in group.into_iter().skip(1).rev() {
+                arms.remove(j);
+            }
+            rules.push(rule);
+            return;
+        }
+    }
+}
+
+/// Rewrites an expression once, bottom up, applying each rule which matches a node.
+fn rewrite(expression: Expression, rules: &mut Vec<SimplificationRule>) -> Expression {
+    match expression {
+        Expression::Char(c) => Expression::Char(c),
+        Expression::Class(chars) => Expression::Class(chars),
+        Expression::EmptyString => Expression::EmptyString,
+        Expression::EmptySet => Expression::EmptySet,
+        Expression::Assertion(assertion) => Expression::Assertion(assertion),
+        Expression::Concatenation(sub_expressions) => {
+            let mut factors = Vec::new();
+            for sub_expression in sub_expressions {
+                match rewrite(sub_expression, rules) {
+                    Expression::Concatenation(inner) => {
+                        rules.push(SimplificationRule::FlattenConcatenation);
+                        factors.extend(inner);
+                    }
+                    Expression::EmptyString => {
+                        rules.push(SimplificationRule::EmptyStringInConcatenation);
+                    }
+                    sub_expression => factors.push(sub_expression),
+                }
+            }
+            if factors.len() > 1 && factors.contains(&Expression::EmptySet) {
+                rules.push(SimplificationRule::EmptySetInConcatenation);
+                return Expression::EmptySet;
+            }
+            match factors.len() {
+                0 => {
+                    rules.push(SimplificationRule::EmptyConcatenation);
+                    Expression::EmptyString
+                }
+                1 => {
+                    rules.push(SimplificationRule::UnwrapSingleton);
+                    factors.pop().unwrap()
+                }
+                _ => Expression::Concatenation(factors),
+            }
+        }
+        Expression::Choice(sub_expressions) => {
+            let mut arms: Vec<Expression> = Vec::new();
+            for sub_expression in sub_expressions {
+                let sub_arms = match rewrite(sub_expression, rules) {
+                    Expression::Choice(inner) => {
+                        rules.push(SimplificationRule::FlattenChoice);
+                        inner
+                    }
+                    sub_expression => vec![sub_expression],
+                };
+                for arm in sub_arms {
+                    match arms.contains(&arm) {
+                        true => rules.push(SimplificationRule::DuplicateArm),
+                        false => arms.push(arm),
+                    }
+                }
+            }
+            if arms.len() > 1 && arms.contains(&Expression::EmptySet) {
+                rules.push(SimplificationRule::EmptySetInChoice);
+                arms.retain(|arm| arm != &Expression::EmptySet);
+            }
+            let is_empty_string_redundant = arms
+                .iter()
+                .any(|arm| arm != &Expression::EmptyString && arm.is_nullable());
+            if is_empty_string_redundant && arms.contains(&Expression::EmptyString) {
+                rules.push(SimplificationRule::RedundantEmptyString);
+                arms.retain(|arm| arm != &Expression::EmptyString);
+            }
+            factor_arms(&mut arms, rules);
+            match arms.len() {
+                1 => {
+                    rules.push(SimplificationRule::UnwrapSingleton);
+                    arms.pop().unwrap()
+                }
+                _ => Expression::Choice(arms),
+            }
+        }
+        Expression::Closure(sub_expression) => match rewrite(*sub_expression, rules) {
+            Expression::EmptyString => {
+                rules.push(SimplificationRule::ClosureOfEmptyString);
+                Expression::EmptyString
+            }
+            Expression::EmptySet => {
+                rules.push(SimplificationRule::ClosureOfEmptySet);
+                Expression::EmptyString
+            }
+            Expression::Closure(inner) => {
+                rules.push(SimplificationRule::NestedClosure);
+                Expression::Closure(inner)
+            }
+            Expression::Choice(arms) => {
+                let mut closed_arms = Vec::new();
+                for arm in arms {
+                    match arm {
+                        Expression::EmptyString => {
+                            rules.push(SimplificationRule::EmptyStringInClosure);
+                        }
+                        Expression::Closure(inner) => {
+                            rules.push(SimplificationRule::ClosureInClosedChoice);
+                            closed_arms.push(*inner);
+                        }
+                        arm => closed_arms.push(arm),
+                    }
+                }
+                Expression::Closure(Box::from(Expression::Choice(closed_arms)))
+            }
+            sub_expression => Expression::Closure(Box::from(sub_expression)),
+        },
+    }
+}
+
+impl Expression {
+    /// Returns an equivalent expression simplified by identities of Kleene algebra, along with
+    /// the rules which were applied.
+    ///
+    /// The expression is rewritten bottom up, applying every [`SimplificationRule`] which
+    /// matches, until no rule matches anywhere. No rule adds leaves, so the simplified
+    /// expression never has more leaves than the original, and usually has fewer for the
+    /// annotator to number.
+    pub fn simplify(&self) -> Simplification {
+        let mut rules = Vec::new();
+        let mut expression = self.clone();
+        loop {
+            let n_rules = rules.len();
+            expression = rewrite(expression, &mut rules);
+            if rules.len() == n_rules {
+                return Simplification { expression, rules };
+            }
+        }
+    }
+}
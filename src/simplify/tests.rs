@@ -0,0 +1,132 @@
+use super::*;
+use crate::annotator::annotate_ast;
+use crate::dfa::generate_dfa;
+use crate::lexer::{generate_token_map, lex_string};
+use crate::parser::parse;
+
+/// Lexes and parses a raw expression over the alphabet "abc".
+fn parse_str(raw_expression: &str) -> Expression {
+    let token_map = generate_token_map("abc").unwrap();
+    parse(lex_string(&token_map, raw_expression).unwrap()).unwrap()
+}
+
+/// Returns the number of leaves of an expression.
+fn count_leaves(expression: &Expression) -> usize {
+    match expression {
+        Expression::Concatenation(sub_expressions) | Expression::Choice(sub_expressions) => {
+            sub_expressions.iter().map(count_leaves).sum()
+        }
+        Expression::Closure(sub_expression) => count_leaves(sub_expression),
+        Expression::Char(_)
+        | Expression::Class(_)
+        | Expression::EmptyString
+        | Expression::EmptySet
+        | Expression::Assertion(_) => 1,
+    }
+}
+
+#[test]
+fn test_each_rule() {
+    let cases = [
+        ("a|(b|c)", "a|b|c", SimplificationRule::FlattenChoice),
+        ("a(bc)", "abc", SimplificationRule::FlattenConcatenation),
+        ("a|a", "a", SimplificationRule::DuplicateArm),
+        ("()|a*", "a*", SimplificationRule::RedundantEmptyString),
+        ("()a", "a", SimplificationRule::EmptyStringInConcatenation),
+        ("()*", "()", SimplificationRule::ClosureOfEmptyString),
+        ("a∅b", "∅", SimplificationRule::EmptySetInConcatenation),
+        ("∅|a", "a", SimplificationRule::EmptySetInChoice),
+        ("∅*", "()", SimplificationRule::ClosureOfEmptySet),
+        ("(a*)*", "a*", SimplificationRule::NestedClosure),
+        ("(()|a)*", "a*", SimplificationRule::EmptyStringInClosure),
+        (
+            "(a*|b)*",
+            "(a|b)*",
+            SimplificationRule::ClosureInClosedChoice,
+        ),
+        ("ab|ac", "a(b|c)", SimplificationRule::CommonPrefix),
+        ("ba|ca", "(b|c)a", SimplificationRule::CommonSuffix),
+    ];
+    for (raw_expression, expected_output, expected_rule) in cases {
+        let simplification = parse_str(raw_expression).simplify();
+        assert_eq!(
+            simplification.expression().to_string(),
+            expected_output,
+            "{}",
+            raw_expression
+        );
+        assert!(
+            simplification.rules().contains(&expected_rule),
+            "{} {:?}",
+            raw_expression,
+            simplification.rules()
+        );
+    }
+}
+
+#[test]
+fn test_empty_concatenation() {
+    let simplification = Expression::Concatenation(vec![]).simplify();
+    assert_eq!(simplification.expression(), &Expression::EmptyString);
+    assert_eq!(
+        simplification.rules(),
+        [SimplificationRule::EmptyConcatenation]
+    );
+}
+
+#[test]
+fn test_simplify_reaches_fixed_point() {
+    let cases = [
+        ("((a|a)*)*|()", "a*"),
+        ("abc|abb|ab", "ab(c|b|())"),
+        ("(()|(a|b)*)*c()", "(a|b)*c"),
+        ("(a∅|∅*)b", "b"),
+    ];
+    for (raw_expression, expected_output) in cases {
+        let simplification = parse_str(raw_expression).simplify();
+        assert_eq!(
+            simplification.expression().to_string(),
+            expected_output,
+            "{}",
+            raw_expression
+        );
+        assert!(simplification.expression().simplify().rules().is_empty());
+    }
+}
+
+#[test]
+fn test_simple_expression_is_unchanged() {
+    let expression = parse_str("(a|b)*abb");
+    let simplification = expression.simplify();
+    assert_eq!(simplification.rules(), []);
+    assert_eq!(simplification.into_expression(), expression);
+}
+
+#[test]
+fn test_simplify_preserves_language_and_reduces_leaves() {
+    let cases = [
+        "ab|ac|a",
+        "(a*b*)*|()|a",
+        "(ab|cb)*(a|a)",
+        "((a|())(b|()))*",
+        "abc|bc|c",
+        "a(b|c)|a(c|b)",
+    ];
+    for raw_expression in cases {
+        let expression = parse_str(raw_expression);
+        let simplified = expression.simplify().into_expression();
+        assert!(
+            count_leaves(&simplified) <= count_leaves(&expression),
+            "{}",
+            raw_expression
+        );
+        let alphabet: std::collections::BTreeSet<char> = "abc".chars().collect();
+        let original_dfa = generate_dfa(annotate_ast(expression).unwrap(), alphabet.clone());
+        let simplified_dfa = generate_dfa(annotate_ast(simplified).unwrap(), alphabet);
+        assert!(
+            simplified_dfa.is_equivalent(&original_dfa),
+            "{}",
+            raw_expression
+        );
+    }
+}
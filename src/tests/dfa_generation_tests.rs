@@ -11,6 +11,7 @@ fn test_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::new(),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -25,6 +26,7 @@ fn test_character() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -52,6 +54,7 @@ fn test_closure_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::new(),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -83,6 +86,7 @@ fn test_closure_closure_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::new(),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -115,6 +119,7 @@ fn test_closure_character() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 0)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -129,6 +134,7 @@ fn test_closure_closure_character() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 0)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -146,6 +152,7 @@ fn test_concatenated_closure_character() {
             (0, HashMap::from([('a', 0), ('b', 1)])),
             (1, HashMap::from([('b', 1)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -163,6 +170,7 @@ fn test_concatenated_character_and_closure_character() {
             (0, HashMap::from([('a', 1)])),
             (1, HashMap::from([('b', 1)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -177,6 +185,7 @@ fn test_concatenated_closure_character_and_character() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 0), ('b', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -194,6 +203,7 @@ fn test_closure_concatenation() {
             (0, HashMap::from([('a', 1)])),
             (1, HashMap::from([('b', 0)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -201,11 +211,9 @@ fn test_closure_concatenation() {
 
 #[test]
 fn test_multiple_concatenated_closures() {
-    // This test needs to check many possible outputs, because DFA generated depends on
-    // a random access to a HashMap and thus could follow one of many branches first.
     let input_expression = "ab*c*d";
     let input_alphabet = "abcd";
-    let expected_output_a = dfa::Dfa {
+    let expected_output = dfa::Dfa {
         n_states: 4,
         start_state: 0,
         accepting_states: HashSet::from([3]),
@@ -214,19 +222,10 @@ fn test_multiple_concatenated_closures() {
             (1, HashMap::from([('b', 1), ('c', 2), ('d', 3)])),
             (2, HashMap::from([('c', 2), ('d', 3)])),
         ]),
-    };
-    let expected_output_b = dfa::Dfa {
-        n_states: 4,
-        start_state: 0,
-        accepting_states: HashSet::from([2]),
-        transition_function: HashMap::from([
-            (0, HashMap::from([('a', 1)])),
-            (1, HashMap::from([('b', 1), ('d', 2), ('c', 3)])),
-            (3, HashMap::from([('c', 3), ('d', 2)])),
-        ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
-    assert!(output == expected_output_a || output == expected_output_b);
+    assert_eq!(output, expected_output);
 }
 
 #[test]
@@ -242,6 +241,7 @@ fn test_concatenated_choice_closure() {
             (1, HashMap::from([('a', 1), ('b', 2)])),
             (2, HashMap::from([('a', 1), ('b', 2)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -256,6 +256,7 @@ fn test_choice_character_character() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1), ('b', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -270,6 +271,7 @@ fn test_choice_character_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([0, 1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -300,6 +302,7 @@ fn test_choice_empty_string_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([0]),
         transition_function: HashMap::new(),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -333,6 +336,7 @@ fn test_choice_character_concatenation() {
             (0, HashMap::from([('a', 1)])),
             (1, HashMap::from([('b', 2)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -340,12 +344,9 @@ fn test_choice_character_concatenation() {
 
 #[test]
 fn test_choice_concatenation_concatenation() {
-    // This test needs to check two possible outputs, because DFA generated depends on
-    // a random access to a HashMap and thus could follow either of the "ab" or "ba"
-    // branch first.
     let input_expression = "ab|ba";
     let input_alphabet = "ab";
-    let expected_output_a = dfa::Dfa {
+    let expected_output = dfa::Dfa {
         n_states: 4,
         start_state: 0,
         accepting_states: HashSet::from([3]),
@@ -354,19 +355,10 @@ fn test_choice_concatenation_concatenation() {
             (1, HashMap::from([('b', 3)])),
             (2, HashMap::from([('a', 3)])),
         ]),
-    };
-    let expected_output_b = dfa::Dfa {
-        n_states: 4,
-        start_state: 0,
-        accepting_states: HashSet::from([3]),
-        transition_function: HashMap::from([
-            (0, HashMap::from([('b', 1), ('a', 2)])),
-            (1, HashMap::from([('a', 3)])),
-            (2, HashMap::from([('b', 3)])),
-        ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
-    assert!(output == expected_output_a || output == expected_output_b);
+    assert_eq!(output, expected_output);
 }
 
 #[test]
@@ -381,6 +373,7 @@ fn test_concatenated_choice_character() {
             (0, HashMap::from([('a', 1), ('b', 1)])),
             (1, HashMap::from([('a', 2)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -398,6 +391,7 @@ fn test_concatenated_character_choice() {
             (0, HashMap::from([('a', 1)])),
             (1, HashMap::from([('a', 2), ('b', 2)])),
         ]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -412,6 +406,7 @@ fn test_redundant_parentheses() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -426,6 +421,7 @@ fn test_concatenated_char_and_empty_string() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
@@ -440,7 +436,93 @@ fn test_concatenated_empty_string_and_char() {
         start_state: 0,
         accepting_states: HashSet::from([1]),
         transition_function: HashMap::from([(0, HashMap::from([('a', 1)]))]),
+        alphabet: input_alphabet.chars().collect(),
+    };
+    let output = generate_dfa(input_expression, input_alphabet).unwrap();
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn test_empty_set() {
+    let input_expression = "∅";
+    let input_alphabet = "ab";
+    let expected_output = dfa::Dfa {
+        n_states: 1,
+        start_state: 0,
+        accepting_states: HashSet::new(),
+        transition_function: HashMap::new(),
+        alphabet: input_alphabet.chars().collect(),
     };
     let output = generate_dfa(input_expression, input_alphabet).unwrap();
     assert_eq!(output, expected_output);
 }
+
+#[test]
+fn test_empty_set_in_expressions() {
+    let cases = [("a∅b|b", "b"), ("∅*a", "a"), ("(a|∅)*", "a*")];
+    for (input_expression, equivalent_expression) in cases {
+        let output = generate_dfa(input_expression, "ab").unwrap();
+        let expected_output = generate_dfa(equivalent_expression, "ab").unwrap();
+        assert!(
+            output.is_equivalent(&expected_output),
+            "{}",
+            input_expression
+        );
+    }
+}
+
+#[test]
+fn test_property_intersected_with_alphabet() {
+    let output = generate_dfa("\\p{Lu}\\p{Greek}*", "aAbBαΩ").unwrap();
+    let expected_output = generate_dfa("(A|B|Ω)(α|Ω)*", "aAbBαΩ").unwrap();
+    assert!(output.is_equivalent(&expected_output));
+    assert_eq!(output.alphabet, "aAbBαΩ".chars().collect());
+}
+
+#[test]
+fn test_property_outside_alphabet() {
+    let output = generate_dfa("a|\\p{Nd}", "ab").unwrap();
+    let expected_output = generate_dfa("a", "ab").unwrap();
+    assert!(output.is_equivalent(&expected_output));
+}
+
+#[test]
+fn test_assertions() {
+    let cases = [
+        ("^a", "a", "ba"),
+        ("b*(^a|b)", "a", "ba"),
+        ("a$", "a", "ab"),
+        ("a\\b b", "a b", "ab"),
+        ("a\\Bb", "ab", "a b"),
+        ("\\b(a| )", "a", " "),
+        ("\\B", "", " "),
+    ];
+    for (input_expression, accepted, rejected) in cases {
+        let output = generate_dfa(input_expression, "ab ").unwrap();
+        assert!(output.accepts(accepted), "{}", input_expression);
+        assert!(!output.accepts(rejected), "{}", input_expression);
+    }
+}
+
+#[test]
+fn test_assertion_states_record_look_behind() {
+    // The states after " " and "a" have the same positions, but only one is at a boundary.
+    let output = generate_dfa("(a| )\\ba", "a ").unwrap();
+    let expected_output = dfa::Dfa {
+        n_states: 4,
+        start_state: 0,
+        accepting_states: HashSet::from([3]),
+        transition_function: HashMap::from([
+            (0, HashMap::from([(' ', 1), ('a', 2)])),
+            (1, HashMap::from([('a', 3)])),
+        ]),
+        alphabet: "a ".chars().collect(),
+    };
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn test_anchors_are_reserved() {
+    assert!(generate_dfa("a", "a^").is_err());
+    assert!(generate_dfa("a", "a$").is_err());
+}
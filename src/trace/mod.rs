@@ -0,0 +1,153 @@
+//! Records the steps of the subset construction which generates a DFA.
+
+use crate::construction::format_state;
+use crate::dfa::Neighbour;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents a single step of the subset construction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceEvent {
+    /// A new state was found, representing a set of positions and look-behind not seen
+    /// before. It is added to the worklist of unmarked states.
+    StateDiscovered {
+        /// The index given to the state.
+        state: usize,
+        /// The positions represented by the state.
+        positions: BTreeSet<usize>,
+        /// The neighbour before the state, if the positions contain an assertion. States with
+        /// the same positions are told apart by their look-behind.
+        look_behind: Option<Neighbour>,
+    },
+    /// A state was taken from the worklist, so its transitions are about to be added.
+    StateMarked {
+        /// The index of the state.
+        state: usize,
+    },
+    /// A transition was added to the DFA.
+    TransitionAdded {
+        /// The state the transition leaves.
+        from: usize,
+        /// The char the transition is taken on.
+        c: char,
+        /// The state the transition enters.
+        to: usize,
+    },
+    /// A state was found to contain the terminal position, so is accepting.
+    AcceptingStateIdentified {
+        /// The index of the state.
+        state: usize,
+    },
+}
+
+impl Display for TraceEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            TraceEvent::StateDiscovered {
+                state,
+                positions,
+                look_behind,
+            } => write!(
+                f,
+                "Discovered state {} = {}",
+                state,
+                format_state(positions, *look_behind)
+            ),
+            TraceEvent::StateMarked { state } => write!(f, "Marked state {}", state),
+            TraceEvent::TransitionAdded { from, c, to } => {
+                write!(f, "Added transition {} --{:?}--> {}", from, c, to)
+            }
+            TraceEvent::AcceptingStateIdentified { state } => {
+                write!(f, "Identified state {} as accepting", state)
+            }
+        }
+    }
+}
+
+/// An ordered log of the steps of the subset construction, from which the derivation of each
+/// state of the DFA can be replayed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Trace {
+    pub(crate) events: Vec<TraceEvent>,
+}
+
+/// Escapes the chars of a string which would otherwise end a Markdown table cell.
+fn escape_markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+impl Trace {
+    /// The steps of the construction, in the order they were taken.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Renders the trace as plain text, with one numbered step per line.
+    pub fn render_text(&self) -> String {
+        let lines: Vec<String> = self
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| format!("{:>4}. {}", i + 1, event))
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Renders the trace as a Markdown table, with one row per step.
+    pub fn render_markdown(&self) -> String {
+        let mut lines = vec![
+            String::from("| Step | Event | State | Char | Target | Positions |"),
+            String::from("| ---: | --- | ---: | --- | ---: | --- |"),
+        ];
+        for (i, event) in self.events.iter().enumerate() {
+            let (name, state, c, target, positions) = match event {
+                TraceEvent::StateDiscovered {
+                    state,
+                    positions,
+                    look_behind,
+                } => (
+                    "Discovered",
+                    state.to_string(),
+                    String::new(),
+                    String::new(),
+                    format_state(positions, *look_behind),
+                ),
+                TraceEvent::StateMarked { state } => (
+                    "Marked",
+                    state.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ),
+                TraceEvent::TransitionAdded { from, c, to } => (
+                    "Transition",
+                    from.to_string(),
+                    format!("`{}`", escape_markdown_cell(&c.to_string())),
+                    to.to_string(),
+                    String::new(),
+                ),
+                TraceEvent::AcceptingStateIdentified { state } => (
+                    "Accepting",
+                    state.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ),
+            };
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} | {} |",
+                i + 1,
+                name,
+                state,
+                c,
+                target,
+                positions
+            ));
+        }
+        lines.join("\n")
+    }
+}
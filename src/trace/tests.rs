@@ -0,0 +1,154 @@
+use super::*;
+use crate::explain;
+
+#[test]
+fn test_trace_events() {
+    let construction = explain("ab", "ab").unwrap();
+    let expected_output = vec![
+        TraceEvent::StateDiscovered {
+            state: 0,
+            positions: BTreeSet::from([0]),
+            look_behind: None,
+        },
+        TraceEvent::StateMarked { state: 0 },
+        TraceEvent::StateDiscovered {
+            state: 1,
+            positions: BTreeSet::from([1]),
+            look_behind: None,
+        },
+        TraceEvent::TransitionAdded {
+            from: 0,
+            c: 'a',
+            to: 1,
+        },
+        TraceEvent::StateMarked { state: 1 },
+        TraceEvent::StateDiscovered {
+            state: 2,
+            positions: BTreeSet::from([2]),
+            look_behind: None,
+        },
+        TraceEvent::AcceptingStateIdentified { state: 2 },
+        TraceEvent::TransitionAdded {
+            from: 1,
+            c: 'b',
+            to: 2,
+        },
+        TraceEvent::StateMarked { state: 2 },
+    ];
+    assert_eq!(construction.trace().events(), expected_output);
+}
+
+#[test]
+fn test_nullable_start_state_is_accepting() {
+    let construction = explain("a*", "a").unwrap();
+    assert_eq!(
+        &construction.trace().events()[..2],
+        [
+            TraceEvent::StateDiscovered {
+                state: 0,
+                positions: BTreeSet::from([0, 1]),
+                look_behind: None,
+            },
+            TraceEvent::AcceptingStateIdentified { state: 0 },
+        ]
+    );
+}
+
+// Replaying the trace should rebuild exactly the DFA it was recorded from.
+#[test]
+fn test_trace_replays_dfa() {
+    let construction = explain("(a|b)*abb|ba*", "ab").unwrap();
+    let dfa = construction.dfa();
+    let mut n_states = 0;
+    let mut accepting_states = std::collections::HashSet::new();
+    let mut n_transitions = 0;
+    for event in construction.trace().events() {
+        match event {
+            TraceEvent::StateDiscovered {
+                state,
+                positions,
+                look_behind,
+            } => {
+                assert_eq!(*state, n_states);
+                assert_eq!(construction.state_positions(*state), Some(positions));
+                assert_eq!(construction.state_look_behind(*state), *look_behind);
+                n_states += 1;
+            }
+            TraceEvent::TransitionAdded { from, c, to } => {
+                assert_eq!(dfa.next_state(*from, *c), Some(*to));
+                n_transitions += 1;
+            }
+            TraceEvent::AcceptingStateIdentified { state } => {
+                assert!(accepting_states.insert(*state));
+            }
+            TraceEvent::StateMarked { .. } => (),
+        }
+    }
+    assert_eq!(n_states, dfa.n_states);
+    assert_eq!(accepting_states, dfa.accepting_states);
+    let expected_transitions: usize = dfa.transition_function.values().map(|t| t.len()).sum();
+    assert_eq!(n_transitions, expected_transitions);
+}
+
+#[test]
+fn test_render_text() {
+    let construction = explain("a", "a").unwrap();
+    let expected_output = [
+        "   1. Discovered state 0 = {0}",
+        "   2. Marked state 0",
+        "   3. Discovered state 1 = {1}",
+        "   4. Identified state 1 as accepting",
+        "   5. Added transition 0 --'a'--> 1",
+        "   6. Marked state 1",
+    ]
+    .join("\n");
+    assert_eq!(construction.trace().render_text(), expected_output);
+}
+
+// States with the same positions but a different look-behind are told apart.
+#[test]
+fn test_render_text_with_look_behind() {
+    let construction = explain("(a| )\\ba", "a ").unwrap();
+    let expected_output = [
+        "   1. Discovered state 0 = {0, 1}",
+        "   2. Marked state 0",
+        "   3. Discovered state 1 = {2} after non-word",
+        "   4. Added transition 0 --' '--> 1",
+        "   5. Discovered state 2 = {2} after word",
+        "   6. Added transition 0 --'a'--> 2",
+        "   7. Marked state 1",
+        "   8. Discovered state 3 = {4}",
+        "   9. Identified state 3 as accepting",
+        "  10. Added transition 1 --'a'--> 3",
+        "  11. Marked state 2",
+        "  12. Marked state 3",
+    ]
+    .join("\n");
+    assert_eq!(construction.trace().render_text(), expected_output);
+}
+
+#[test]
+fn test_render_markdown() {
+    let trace = Trace {
+        events: vec![
+            TraceEvent::StateDiscovered {
+                state: 0,
+                positions: BTreeSet::from([0, 1]),
+                look_behind: None,
+            },
+            TraceEvent::TransitionAdded {
+                from: 0,
+                c: '|',
+                to: 1,
+            },
+        ],
+    };
+    let expected_output = [
+        "| Step | Event | State | Char | Target | Positions |",
+        "| ---: | --- | ---: | --- | ---: | --- |",
+        "| 1 | Discovered | 0 |  |  | {0, 1} |",
+        "| 2 | Transition | 0 | `\\|` | 1 |  |",
+    ]
+    .join("\n");
+    assert_eq!(trace.render_markdown(), expected_output);
+}
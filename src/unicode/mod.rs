@@ -0,0 +1,142 @@
+//! Looks up the chars with a Unicode property, from tables embedded in the crate.
+//!
+//! The tables are generated from the Unicode Character Database by
+//! `scripts/generate_unicode_tables.pl`, so no data is fetched when building.
+
+use crate::char_set::CharSet;
+use std::sync::OnceLock;
+use tables::{GENERAL_CATEGORIES, SCRIPTS};
+
+#[rustfmt::skip]
+mod tables;
+
+#[cfg(test)]
+mod tests;
+
+/// The general categories which group several two-letter categories, by short name, long
+/// name and the short names of the categories they group.
+const GROUPED_CATEGORIES: &[(&str, &str, &[&str])] = &[
+    ("L", "Letter", &["Lu", "Ll", "Lt", "Lm", "Lo"]),
+    ("LC", "Cased_Letter", &["Lu", "Ll", "Lt"]),
+    ("M", "Mark", &["Mn", "Mc", "Me"]),
+    ("N", "Number", &["Nd", "Nl", "No"]),
+    (
+        "P",
+        "Punctuation",
+        &["Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po"],
+    ),
+    ("S", "Symbol", &["Sm", "Sc", "Sk", "So"]),
+    ("Z", "Separator", &["Zs", "Zl", "Zp"]),
+    ("C", "Other", &["Cc", "Cf", "Cs", "Co", "Cn"]),
+];
+
+/// Returns the set of chars in a table of ranges.
+///
+/// The generator merges the ranges of each table as [`CharSet::from_ranges`] does, so they are
+/// used as they are.
+fn table_char_set(ranges: &[(char, char)]) -> CharSet {
+    CharSet {
+        ranges: ranges.to_vec(),
+    }
+}
+
+/// Returns the canonical name of a property along with its chars, or [`None`] if there is no
+/// property with that name.
+///
+/// Names are general categories, such as `L`, `Lu` or `Uppercase_Letter`, or scripts, such as
+/// `Greek` or `Grek`, and are matched exactly. The canonical name is the short name of a
+/// general category, or the long name of a script.
+pub(crate) fn property(name: &str) -> Option<(&'static str, CharSet)> {
+    for (short_name, long_name, ranges) in GENERAL_CATEGORIES {
+        if name == *short_name || name == *long_name {
+            return Some((short_name, table_char_set(ranges)));
+        }
+    }
+    for (short_name, long_name, categories) in GROUPED_CATEGORIES {
+        if name == *short_name || name == *long_name {
+            let mut chars = CharSet::new();
+            for category in *categories {
+                chars = chars.union(&property(category).unwrap().1);
+            }
+            return Some((short_name, chars));
+        }
+    }
+    for (short_name, long_name, ranges) in SCRIPTS {
+        if name == *short_name || name == *long_name {
+            return Some((long_name, table_char_set(ranges)));
+        }
+    }
+    None
+}
+
+/// Returns the canonical name of every property along with its chars, in the order in which
+/// [`property_name`] prefers them.
+///
+/// The sets are built from the tables on first use.
+fn named_properties() -> &'static [(&'static str, CharSet)] {
+    static NAMED_PROPERTIES: OnceLock<Vec<(&'static str, CharSet)>> = OnceLock::new();
+    NAMED_PROPERTIES.get_or_init(|| {
+        GENERAL_CATEGORIES
+            .iter()
+            .map(|(short_name, _, _)| short_name)
+            .chain(
+                GROUPED_CATEGORIES
+                    .iter()
+                    .map(|(short_name, _, _)| short_name),
+            )
+            .chain(SCRIPTS.iter().map(|(_, long_name, _)| long_name))
+            .map(|name| property(name).unwrap())
+            .collect()
+    })
+}
+
+/// Returns the canonical name of the property with exactly the given chars, or [`None`] if
+/// there is no such property.
+pub(crate) fn property_name(chars: &CharSet) -> Option<&'static str> {
+    named_properties()
+        .iter()
+        .find(|(_, property_chars)| property_chars == chars)
+        .map(|(name, _)| *name)
+}
+
+/// The general categories of the word chars, as matched around a word boundary.
+const WORD_CATEGORIES: &[&str] = &["L", "M", "Nd", "Pc"];
+
+/// Returns the word chars: the letters, marks, decimal digits and connector punctuation.
+///
+/// The set is built from the tables on first use.
+pub(crate) fn word_chars() -> &'static CharSet {
+    static WORD_CHARS: OnceLock<CharSet> = OnceLock::new();
+    WORD_CHARS.get_or_init(|| {
+        WORD_CATEGORIES
+            .iter()
+            .fold(CharSet::new(), |chars, category| {
+                chars.union(&property(category).unwrap().1)
+            })
+    })
+}
+
+/// Returns the chars which differ from their lowercase or uppercase forms.
+///
+/// Every other char is its own only case variant. The set is built on first use, by checking
+/// every char.
+pub(crate) fn cased_chars() -> &'static CharSet {
+    static CASED_CHARS: OnceLock<CharSet> = OnceLock::new();
+    CASED_CHARS.get_or_init(|| {
+        CharSet::all()
+            .chars()
+            .filter(|c| !c.to_lowercase().eq([*c]) || !c.to_uppercase().eq([*c]))
+            .collect()
+    })
+}
+
+impl CharSet {
+    /// Returns the chars with a Unicode property, or [`None`] if there is no property with
+    /// that name.
+    ///
+    /// Names are general categories, such as `L`, `Lu` or `Uppercase_Letter`, or scripts, such
+    /// as `Greek` or `Grek`, and are matched exactly.
+    pub fn property(name: &str) -> Option<CharSet> {
+        property(name).map(|(_, chars)| chars)
+    }
+}
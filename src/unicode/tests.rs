@@ -0,0 +1,87 @@
+use super::*;
+
+#[test]
+fn test_general_categories() {
+    let letters = CharSet::property("L").unwrap();
+    assert_eq!(CharSet::property("Letter"), Some(letters.clone()));
+    for c in "aZéλЖ字".chars() {
+        assert!(letters.contains(c), "{}", c);
+    }
+    for c in "1 _-".chars() {
+        assert!(!letters.contains(c), "{}", c);
+    }
+    let uppercase = CharSet::property("Lu").unwrap();
+    assert!(uppercase.contains('Λ'));
+    assert!(!uppercase.contains('λ'));
+    assert!(CharSet::property("Nd").unwrap().contains('٣'));
+}
+
+#[test]
+fn test_scripts() {
+    let greek = CharSet::property("Greek").unwrap();
+    assert_eq!(CharSet::property("Grek"), Some(greek.clone()));
+    assert!(greek.contains('λ'));
+    assert!(!greek.contains('l'));
+    assert!(CharSet::property("Latin").unwrap().contains('l'));
+    assert!(CharSet::property("Common").unwrap().contains('1'));
+}
+
+#[test]
+fn test_unknown_property() {
+    assert_eq!(CharSet::property("Klingon"), None);
+    assert_eq!(CharSet::property("lu"), None);
+}
+
+#[test]
+fn test_property_name() {
+    for (name, canonical_name) in [("Letter", "L"), ("Lu", "Lu"), ("Grek", "Greek")] {
+        let chars = CharSet::property(name).unwrap();
+        assert_eq!(property_name(&chars), Some(canonical_name));
+    }
+    assert_eq!(property_name(&CharSet::from_ranges(['a'..='b'])), None);
+}
+
+#[test]
+fn test_categories_partition_all_chars() {
+    let mut union = CharSet::new();
+    let mut n_chars = 0;
+    for category in ["L", "M", "N", "P", "S", "Z", "C"] {
+        let chars = CharSet::property(category).unwrap();
+        n_chars += chars.len();
+        union = union.union(&chars);
+    }
+    assert_eq!(union, CharSet::all());
+    assert_eq!(n_chars, CharSet::all().len());
+}
+
+#[test]
+fn test_word_chars() {
+    let chars = word_chars();
+    for c in ['a', 'Z', 'α', '漢', '7', '_', '\u{301}'] {
+        assert!(chars.contains(c), "{:?}", c);
+    }
+    for c in [' ', '-', '.', '\u{2167}', '\0'] {
+        assert!(!chars.contains(c), "{:?}", c);
+    }
+}
+
+#[test]
+fn test_cased_chars() {
+    let chars = cased_chars();
+    for c in ['a', 'Z', 'α', 'ß', 'K', '\u{212A}', 'Ⅰ'] {
+        assert!(chars.contains(c), "{}", c);
+    }
+    for c in ['1', '_', '漢', 'ª'] {
+        assert!(!chars.contains(c), "{}", c);
+    }
+}
+
+#[test]
+fn test_tables_are_normalized() {
+    for (short_name, _, ranges) in GENERAL_CATEGORIES.iter().chain(SCRIPTS) {
+        let normalized = CharSet::from_ranges(ranges.iter().map(|(start, end)| *start..=*end));
+        assert_eq!(table_char_set(ranges), normalized, "{}", short_name);
+    }
+    let unknown = CharSet::property("Unknown").unwrap();
+    assert_eq!(unknown.union(&CharSet::new()), unknown);
+}
@@ -0,0 +1,125 @@
+//! Decodes UTF-8 incrementally, one byte at a time.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::RangeInclusive;
+
+#[cfg(test)]
+mod tests;
+
+/// Runtime error representing that the input bytes are not valid UTF-8.
+#[derive(Debug, PartialEq)]
+pub struct InvalidEncodingError {
+    /// The offset in the input of the first byte of the invalid sequence.
+    pub(crate) offset: usize,
+}
+
+impl Display for InvalidEncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "The input contains an invalid UTF-8 sequence at byte offset {}.",
+            self.offset
+        )
+    }
+}
+
+/// Decodes chars from UTF-8 bytes which are pushed to it one at a time.
+///
+/// Invalid encodings are reported as soon as they can be detected, so an unexpected byte is
+/// reported when it is pushed rather than when the sequence would have ended.
+#[derive(Debug, Default)]
+pub struct Utf8Decoder {
+    /// The bytes of the char currently being decoded.
+    buffer: [u8; 4],
+    /// The number of bytes in buffer which are in use.
+    n_buffered: usize,
+    /// The number of bytes pushed to the decoder so far.
+    offset: usize,
+}
+
+/// Returns the length of the UTF-8 encoding of a char from its first byte, or [`None`] if the
+/// byte cannot start a valid encoding.
+fn encoded_length(first_byte: u8) -> Option<usize> {
+    match first_byte {
+        0x00..=0x7f => Some(1),
+        0xc2..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf4 => Some(4),
+        _ => None,
+    }
+}
+
+/// Returns the bytes which may follow the first byte of an encoding as its second byte.
+///
+/// Excluding the rest of the continuation bytes after some first bytes rejects overlong
+/// encodings, surrogates and chars above `char::MAX` at their second byte.
+fn second_byte_range(first_byte: u8) -> RangeInclusive<u8> {
+    match first_byte {
+        0xe0 => 0xa0..=0xbf,
+        0xed => 0x80..=0x9f,
+        0xf0 => 0x90..=0xbf,
+        0xf4 => 0x80..=0x8f,
+        _ => 0x80..=0xbf,
+    }
+}
+
+impl Utf8Decoder {
+    /// Creates a decoder at the start of the input.
+    pub fn new() -> Utf8Decoder {
+        Utf8Decoder::default()
+    }
+
+    /// Returns an error for the sequence currently buffered, discarding it.
+    fn invalid_sequence(&mut self) -> InvalidEncodingError {
+        let error = InvalidEncodingError {
+            offset: self.offset - self.n_buffered,
+        };
+        self.n_buffered = 0;
+        error
+    }
+
+    /// Pushes the next byte of input, returning the char it completes, if any.
+    pub fn push(&mut self, byte: u8) -> Result<Option<char>, InvalidEncodingError> {
+        self.buffer[self.n_buffered] = byte;
+        self.n_buffered += 1;
+        self.offset += 1;
+        let Some(length) = encoded_length(self.buffer[0]) else {
+            return Err(self.invalid_sequence());
+        };
+        let is_expected = match self.n_buffered {
+            1 => true,
+            2 => second_byte_range(self.buffer[0]).contains(&byte),
+            _ => byte & 0xc0 == 0x80,
+        };
+        if !is_expected {
+            return Err(self.invalid_sequence());
+        }
+        if self.n_buffered < length {
+            return Ok(None);
+        }
+        let decoded = std::str::from_utf8(&self.buffer[..self.n_buffered])
+            .ok()
+            .and_then(|s| s.chars().next());
+        match decoded {
+            Some(c) => {
+                self.n_buffered = 0;
+                Ok(Some(c))
+            }
+            None => Err(self.invalid_sequence()),
+        }
+    }
+
+    /// Returns whether the decoder holds the start of a char which has not yet been completed.
+    pub fn is_incomplete(&self) -> bool {
+        self.n_buffered != 0
+    }
+
+    /// Signals the end of the input, returning an error if it ended part way through a char.
+    pub fn finish(&mut self) -> Result<(), InvalidEncodingError> {
+        match self.is_incomplete() {
+            true => Err(self.invalid_sequence()),
+            false => Ok(()),
+        }
+    }
+}
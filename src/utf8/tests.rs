@@ -0,0 +1,83 @@
+use super::*;
+
+fn decode(input: &[u8]) -> Result<String, InvalidEncodingError> {
+    let mut decoder = Utf8Decoder::new();
+    let mut output = String::new();
+    for byte in input {
+        if let Some(c) = decoder.push(*byte)? {
+            output.push(c);
+        }
+    }
+    decoder.finish()?;
+    Ok(output)
+}
+
+#[test]
+fn test_decodes_valid_input() {
+    let input = "a⟹é🦀";
+    assert_eq!(decode(input.as_bytes()), Ok(String::from(input)));
+}
+
+#[test]
+fn test_invalid_first_byte() {
+    assert_eq!(
+        decode(&[b'a', 0x80, b'a']),
+        Err(InvalidEncodingError { offset: 1 })
+    );
+    assert_eq!(
+        decode(&[0xc0, 0x80]),
+        Err(InvalidEncodingError { offset: 0 })
+    );
+}
+
+#[test]
+fn test_unexpected_byte_reported_early() {
+    let mut decoder = Utf8Decoder::new();
+    assert_eq!(decoder.push(b'a'), Ok(Some('a')));
+    assert_eq!(decoder.push(0xf0), Ok(None));
+    assert_eq!(decoder.push(b'a'), Err(InvalidEncodingError { offset: 1 }));
+}
+
+#[test]
+fn test_surrogate_and_overlong_rejected() {
+    assert_eq!(
+        decode(&[0xed, 0xa0, 0x80]),
+        Err(InvalidEncodingError { offset: 0 })
+    );
+    assert_eq!(
+        decode(&[b'a', 0xe0, 0x80, 0x80]),
+        Err(InvalidEncodingError { offset: 1 })
+    );
+}
+
+// Each of these can be rejected at its second byte, before the sequence would have ended.
+#[test]
+fn test_invalid_second_byte_reported_early() {
+    for (first_byte, second_byte) in [(0xe0, 0x80), (0xed, 0xa0), (0xf0, 0x80), (0xf4, 0x90)] {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.push(first_byte), Ok(None));
+        assert_eq!(
+            decoder.push(second_byte),
+            Err(InvalidEncodingError { offset: 0 }),
+            "{:x} {:x}",
+            first_byte,
+            second_byte
+        );
+    }
+}
+
+#[test]
+fn test_second_byte_range_boundaries() {
+    let chars = ['\u{800}', '\u{d7ff}', '\u{e000}', '\u{10000}', char::MAX];
+    let input: String = chars.iter().collect();
+    assert_eq!(decode(input.as_bytes()), Ok(input));
+}
+
+#[test]
+fn test_truncated_input() {
+    let encoded = "🦀".as_bytes();
+    assert_eq!(
+        decode(&encoded[..3]),
+        Err(InvalidEncodingError { offset: 0 })
+    );
+}